@@ -1,59 +1,164 @@
 use std::hint::black_box;
 use std::time::Duration;
-use criterion::{criterion_group, criterion_main, Criterion};
-use json_deserialize_perf::deser_reader::AssetPreloader as AssetPreloader;
-use json_deserialize_perf::deser_reader_simd::AssetPreloader as SimdAssetPreloader;
-use json_deserialize_perf::deser_simd_alloc_buff_in_one_go::AssetPreloader as SimdAllocAllAssetPreloader;
-use json_deserialize_perf::deser_alloc_buff_in_one_go::AssetPreloader as AllocAllAssetPreloader;
-use json_deserialize_perf::deser_include_str::AssetPreloader as IncludeStrAssetPreloader;
-use json_deserialize_perf::deser_read_string::AssetPreloader as ReadStringAssetPreloader;
-
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use hashbrown::HashMap;
+use json_deserialize_perf::assets::{AssetPreloader, AssetSource, LoadStrategy};
+use json_deserialize_perf::cache::AssetCache;
+use json_deserialize_perf::archive::{AssetArchive, MappedAssetArchive};
+use json_deserialize_perf::deser_borrowed::{AssetBuffers, BorrowedAssets, OwnedAssets};
+use json_deserialize_perf::deser_include_str::IncludeStrSource;
+use json_deserialize_perf::deser_read_string::ReadStringSource;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::enums::EnumRegistry;
+use json_deserialize_perf::models::EngravingData;
+use json_deserialize_perf::skill_index::SkillIndex;
 
+fn bench_strategy(c: &mut Criterion, name: &str, strategy: LoadStrategy) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            black_box(AssetPreloader::with_strategy(strategy).unwrap())
+        })
+    });
+}
 
 fn simd_alloc_all_bench_asset_preloader(c: &mut Criterion) {
-    c.bench_function("SimdAllocAllAssetPreloader", |b| {
+    bench_strategy(c, "SimdAllocAllAssetPreloader", LoadStrategy::SimdAllocBuffInOneGo);
+}
+
+fn alloc_all_bench_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "AllocAllAssetPreloader", LoadStrategy::AllocBuffInOneGo);
+}
+
+fn simd_bench_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "SimdAssetPreloader", LoadStrategy::ReaderSimd);
+}
+
+fn bench_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "AssetPreloader", LoadStrategy::Reader);
+}
+
+fn bench_include_str_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "IncludeStrAssetPreloader", LoadStrategy::IncludeStr);
+}
+
+fn bench_read_string_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "ReadStringAssetPreloader", LoadStrategy::ReadString);
+}
+
+fn bench_parallel_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "ParallelAssetPreloader", LoadStrategy::ParallelReader);
+}
+
+fn bench_parallel_simd_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "ParallelSimdAssetPreloader", LoadStrategy::ParallelReaderSimd);
+}
+
+fn bench_mmap_asset_preloader(c: &mut Criterion) {
+    bench_strategy(c, "MmapAssetPreloader", LoadStrategy::Mmap);
+}
+
+fn bench_cold_cache_asset_preloader(c: &mut Criterion) {
+    let cache = AssetCache::new(std::env::temp_dir().join("json-deserialize-perf-cold.cache"));
+    c.bench_function("ColdCacheAssetPreloader", |b| {
+        b.iter_batched(
+            || {
+                let _ = std::fs::remove_file(cache.path());
+            },
+            |_| black_box(cache.load_or_build(LoadStrategy::default()).unwrap()),
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+fn bench_warm_cache_asset_preloader(c: &mut Criterion) {
+    let cache = AssetCache::new(std::env::temp_dir().join("json-deserialize-perf-warm.cache"));
+    cache.load_or_build(LoadStrategy::default()).unwrap();
+    c.bench_function("WarmCacheAssetPreloader", |b| {
         b.iter(|| {
-            let preloader = SimdAllocAllAssetPreloader::new();
+            black_box(cache.load_or_build(LoadStrategy::default()).unwrap())
         })
     });
 }
 
-fn alloc_all_bench_asset_preloader(c: &mut Criterion) {
-    c.bench_function("AllocAllAssetPreloader", |b| {
+fn bench_archive_asset_preloader(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("json-deserialize-perf.rkyv");
+    AssetArchive::from_preloader(AssetPreloader::new().unwrap()).write(&path).unwrap();
+    c.bench_function("ArchiveAssetPreloader", |b| {
+        b.iter(|| {
+            let archive = MappedAssetArchive::open(&path).unwrap();
+            black_box(archive.get().skill_data.get(black_box(16140)).is_some())
+        })
+    });
+    c.bench_function("UncheckedArchiveAssetPreloader", |b| {
         b.iter(|| {
-            let preloader = AllocAllAssetPreloader::new();
+            let archive = unsafe { MappedAssetArchive::open_unchecked(&path) }.unwrap();
+            black_box(archive.get().skill_data.get(black_box(16140)).is_some())
         })
     });
 }
 
-fn simd_bench_asset_preloader(c: &mut Criterion) {
-    c.bench_function("SimdAssetPreloader", |b| {
+// both sides load exactly `BORROWED_FILES`, only owned vs borrowed models differ
+fn bench_borrowed_asset_preloader(c: &mut Criterion) {
+    let mut group = c.benchmark_group("BorrowedVsOwned");
+    group.bench_function("OwnedReadString", |b| {
         b.iter(|| {
-            let preloader = SimdAssetPreloader::new();
+            black_box(OwnedAssets::load(&mut ReadStringSource).unwrap().skill_data.len())
         })
     });
+    group.bench_function("BorrowedReadString", |b| {
+        b.iter(|| {
+            let buffers = AssetBuffers::read().unwrap();
+            black_box(buffers.parse().unwrap().skill_data.len())
+        })
+    });
+    group.bench_function("OwnedIncludeStr", |b| {
+        b.iter(|| {
+            black_box(OwnedAssets::load(&mut IncludeStrSource).unwrap())
+        })
+    });
+    group.bench_function("BorrowedIncludeStr", |b| {
+        b.iter(|| {
+            black_box(BorrowedAssets::embedded().unwrap())
+        })
+    });
+    group.finish();
 }
 
-fn bench_asset_preloader(c: &mut Criterion) {
-    c.bench_function("AssetPreloader", |b| {
+fn bench_enum_registry(c: &mut Criterion) {
+    c.bench_function("EnumRegistry", |b| {
         b.iter(|| {
-            let preloader = AssetPreloader::new();
+            black_box(EnumRegistry::load(&mut ReaderSource).unwrap())
+        })
+    });
+    c.bench_function("LazyEnumRegistry", |b| {
+        b.iter(|| {
+            black_box(EnumRegistry::load_lazy(&mut ReaderSource).unwrap())
+        })
+    });
+    c.bench_function("LazyEnumRegistryLookup", |b| {
+        b.iter(|| {
+            let registry = EnumRegistry::load_lazy(&mut ReaderSource).unwrap();
+            black_box(registry.name_of(black_box("afkkickchecktype"), black_box(3)).unwrap().is_some())
         })
     });
 }
 
-fn bench_include_str_asset_preloader(c: &mut Criterion) {
-    c.bench_function("IncludeStrAssetPreloader", |b| {
+// the preloader benches already build the index, this isolates its share
+fn bench_skill_index(c: &mut Criterion) {
+    let assets = AssetPreloader::new().unwrap();
+    c.bench_function("SkillIndex", |b| {
         b.iter(|| {
-            let preloader = IncludeStrAssetPreloader::new();
+            black_box(SkillIndex::build(&assets.skill_data, &assets.skill_buff_data, &assets.skill_effect_data))
         })
     });
 }
 
-fn bench_read_string_asset_preloader(c: &mut Criterion) {
-    c.bench_function("ReadStringAssetPreloader", |b| {
+// run once plain and once with `--features slim-engravings` to compare the two models
+fn bench_engraving_data(c: &mut Criterion) {
+    let name = if cfg!(feature = "slim-engravings") { "SlimEngravingData" } else { "EngravingData" };
+    c.bench_function(name, |b| {
         b.iter(|| {
-            let preloader = ReadStringAssetPreloader::new();
+            black_box(ReaderSource.load::<HashMap<u32, EngravingData>>("Ability.json").unwrap())
         })
     });
 }
@@ -73,5 +178,15 @@ criterion_group! {
               bench_asset_preloader,
               bench_include_str_asset_preloader,
               bench_read_string_asset_preloader,
+              bench_parallel_asset_preloader,
+              bench_parallel_simd_asset_preloader,
+              bench_mmap_asset_preloader,
+              bench_cold_cache_asset_preloader,
+              bench_warm_cache_asset_preloader,
+              bench_archive_asset_preloader,
+              bench_borrowed_asset_preloader,
+              bench_enum_registry,
+              bench_engraving_data,
+              bench_skill_index,
 }
 criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::{fs::{self, File}, path::Path};
+use hashbrown::HashMap;
+use memmap2::Mmap;
+use rkyv::{rancor, Archive, Serialize as ArchiveSerialize};
+
+use crate::assets::AssetPreloader;
+use crate::error::AssetError;
+use crate::models::*;
+
+/// Values sorted by id, looked up with a binary search over the id column.
+#[derive(Archive, ArchiveSerialize)]
+pub struct IdTable<V> {
+    ids: Vec<u32>,
+    values: Vec<V>,
+}
+
+impl<V> IdTable<V> {
+    fn from_map<K: Copy>(map: HashMap<K, V>, key: impl Fn(K) -> u32) -> Self {
+        let mut entries: Vec<_> = map.into_iter().map(|(id, value)| (key(id), value)).collect();
+        entries.sort_unstable_by_key(|(id, _)| *id);
+        let (ids, values) = entries.into_iter().unzip();
+        Self { ids, values }
+    }
+}
+
+impl<V: Archive> ArchivedIdTable<V> {
+    pub fn get(&self, id: u32) -> Option<&V::Archived> {
+        let index = self.ids.binary_search_by(|probe| probe.to_native().cmp(&id)).ok()?;
+        self.values.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &V::Archived)> {
+        self.ids.iter().map(|id| id.to_native()).zip(self.values.iter())
+    }
+}
+
+/// The large id keyed tables in a layout that can be read straight out of a mapped file.
+#[derive(Archive, ArchiveSerialize)]
+pub struct AssetArchive {
+    pub combat_effect_data: IdTable<CombatEffectData>,
+    pub engraving_data: IdTable<EngravingData>,
+    pub skill_buff_data: IdTable<SkillBuffData>,
+    pub skill_data: IdTable<SkillData>,
+    pub skill_effect_data: IdTable<SkillEffectData>,
+    pub npc_data: IdTable<Npc>,
+}
+
+impl AssetArchive {
+    pub fn from_preloader(assets: AssetPreloader) -> Self {
+        Self {
+            // negative ids keep their bit pattern, `combat_effect` casts the same way when looking up
+            combat_effect_data: IdTable::from_map(assets.combat_effect_data, |id| id as u32),
+            engraving_data: IdTable::from_map(assets.engraving_data, |id| id),
+            skill_buff_data: IdTable::from_map(assets.skill_buff_data, |id| id),
+            skill_data: IdTable::from_map(assets.skill_data, |id| id),
+            skill_effect_data: IdTable::from_map(assets.skill_effect_data, |id| id),
+            npc_data: IdTable::from_map(assets.npc_data, |id| id),
+        }
+    }
+
+    /// The archive is not stamped with its sources, rewrite it whenever `meter-data` changes.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), AssetError> {
+        let path = path.as_ref().display().to_string();
+        let bytes = rkyv::to_bytes::<rancor::Error>(self).map_err(|err| AssetError::Archive {
+            path: path.clone(),
+            message: err.to_string(),
+        })?;
+        fs::write(&path, bytes).map_err(|err| AssetError::io(&path, err))
+    }
+}
+
+impl ArchivedAssetArchive {
+    pub fn combat_effect(&self, id: i32) -> Option<&ArchivedCombatEffectData> {
+        self.combat_effect_data.get(id as u32)
+    }
+}
+
+pub struct MappedAssetArchive {
+    mmap: Mmap,
+}
+
+impl MappedAssetArchive {
+    /// Maps the archive and validates it once, later accesses are free.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AssetError> {
+        let archive = Self::map(path.as_ref())?;
+        rkyv::access::<ArchivedAssetArchive, rancor::Error>(&archive.mmap).map_err(|err| AssetError::Archive {
+            path: path.as_ref().display().to_string(),
+            message: err.to_string(),
+        })?;
+        Ok(archive)
+    }
+
+    /// Maps the archive without validating it.
+    ///
+    /// # Safety
+    ///
+    /// The file must have been written by [`AssetArchive::write`] with the same version of this crate.
+    pub unsafe fn open_unchecked(path: impl AsRef<Path>) -> Result<Self, AssetError> {
+        Self::map(path.as_ref())
+    }
+
+    fn map(path: &Path) -> Result<Self, AssetError> {
+        let display = path.display().to_string();
+        let file = File::open(path).map_err(|err| AssetError::io(&display, err))?;
+        // SAFETY: the archive is written once and not expected to be modified while mapped
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|err| AssetError::io(&display, err))?;
+        Ok(Self { mmap })
+    }
+
+    pub fn get(&self) -> &ArchivedAssetArchive {
+        // SAFETY: validated in `open`, or vouched for by the caller of `open_unchecked`
+        unsafe { rkyv::access_unchecked::<ArchivedAssetArchive>(&self.mmap) }
+    }
+}
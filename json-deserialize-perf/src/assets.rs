@@ -0,0 +1,204 @@
+use std::io::Read;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use hashbrown::HashMap;
+
+use crate::attribution::Attributor;
+use crate::buff_flags::BuffFlagIndex;
+use crate::error::AssetError;
+use crate::classes::ClassRegistry;
+use crate::gem_skills::GemSkillGroupRegistry;
+use crate::item_sets::ItemSetRegistry;
+use crate::raids::RaidCatalog;
+use crate::skill_index::SkillIndex;
+use crate::stats::StatResolver;
+use crate::support_buffs::SupportBuffGroups;
+use crate::models::*;
+use crate::deser_reader::ReaderSource;
+use crate::deser_reader_simd::SimdReaderSource;
+use crate::deser_alloc_buff_in_one_go::AllocBuffSource;
+use crate::deser_simd_alloc_buff_in_one_go::SimdAllocBuffSource;
+use crate::deser_include_str::IncludeStrSource;
+use crate::deser_read_string::ReadStringSource;
+use crate::deser_parallel::load_parallel;
+use crate::deser_mmap::MmapSource;
+
+pub const ASSET_DIR: &str = "meter-data";
+
+/// Every `meter-data` file [`AssetPreloader`] is built from.
+pub const ASSET_FILES: [&str; 13] = [
+    "CombatEffect.json",
+    "Ability.json",
+    "SkillBuff.json",
+    "Skill.json",
+    "SkillEffect.json",
+    "StatType.json",
+    "Esther.json",
+    "Npc.json",
+    "GemSkillGroup.json",
+    "encounters.json",
+    "PCData.json",
+    "ItemSet.json",
+    "SupportBuffGroup.json",
+];
+
+pub fn asset_path(name: &str) -> String {
+    format!("{ASSET_DIR}/{name}")
+}
+
+// Tracking the path costs a good share of the parse, so every parser below first runs without it and
+// only parses again with `serde_path_to_error` once that failed, to say where.
+
+/// `open` is called a second time to re-read the input when the first parse fails.
+pub fn parse_json_reader<T, R, F>(file: &str, mut open: F) -> Result<T, AssetError>
+where
+    T: DeserializeOwned,
+    R: Read,
+    F: FnMut() -> Result<R, AssetError>,
+{
+    if let Ok(value) = T::deserialize(&mut serde_json::Deserializer::from_reader(open()?)) {
+        return Ok(value);
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_reader(open()?);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|err| AssetError::json(file, err, None))
+}
+
+pub fn parse_json_slice<'a, T: Deserialize<'a>>(file: &str, bytes: &'a [u8]) -> Result<T, AssetError> {
+    if let Ok(value) = T::deserialize(&mut serde_json::Deserializer::from_slice(bytes)) {
+        return Ok(value);
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|err| AssetError::json(file, err, Some(bytes)))
+}
+
+pub fn parse_json_str<'a, T: Deserialize<'a>>(file: &str, string: &'a str) -> Result<T, AssetError> {
+    if let Ok(value) = T::deserialize(&mut serde_json::Deserializer::from_str(string)) {
+        return Ok(value);
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_str(string);
+    serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|err| AssetError::json(file, err, Some(string.as_bytes())))
+}
+
+/// simd-json parses `bytes` in place, `reload` has to hand back the original input for the second parse.
+pub fn parse_simd_slice<T, F>(file: &str, bytes: &mut [u8], reload: F) -> Result<T, AssetError>
+where
+    T: DeserializeOwned,
+    F: FnOnce() -> Result<Vec<u8>, AssetError>,
+{
+    // syntax errors surface while building the tape, before any value is visited
+    let mut deserializer =
+        simd_json::Deserializer::from_slice(bytes).map_err(|err| AssetError::simd(file, ".".into(), err))?;
+    if let Ok(value) = T::deserialize(&mut deserializer) {
+        return Ok(value);
+    }
+
+    let mut original = reload()?;
+    let mut deserializer = simd_json::Deserializer::from_slice(&mut original)
+        .map_err(|err| AssetError::simd(file, ".".into(), err))?;
+    serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|err| AssetError::simd(file, err.path().to_string(), err.into_inner()))
+}
+
+/// A way of turning a `meter-data` file name into a deserialized value.
+///
+/// Strategies only decide how the bytes are acquired and parsed;
+/// building the [`AssetPreloader`] tables is shared.
+pub trait AssetSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStrategy {
+    #[default]
+    Reader,
+    ReaderSimd,
+    AllocBuffInOneGo,
+    SimdAllocBuffInOneGo,
+    IncludeStr,
+    ReadString,
+    ParallelReader,
+    ParallelReaderSimd,
+    Mmap,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AssetPreloader {
+    pub combat_effect_data: HashMap<i32, CombatEffectData>,
+    pub engraving_data: HashMap<u32, EngravingData>,
+    pub skill_buff_data: HashMap<u32, SkillBuffData>,
+    pub buff_flags: BuffFlagIndex,
+    pub skill_data: HashMap<u32, SkillData>,
+    pub skill_effect_data: HashMap<u32, SkillEffectData>,
+    pub skill_index: SkillIndex,
+    pub support_buff_groups: SupportBuffGroups,
+    pub stat_type_map: HashMap<String, u32>,
+    pub esther_data: Vec<Esther>,
+    pub npc_data: HashMap<u32, Npc>,
+    pub gem_skill_groups: GemSkillGroupRegistry,
+    pub raid_catalog: RaidCatalog,
+    pub class_registry: ClassRegistry,
+    pub item_sets: ItemSetRegistry,
+}
+
+impl AssetPreloader {
+    pub fn new() -> Result<Self, AssetError> {
+        Self::with_strategy(LoadStrategy::default())
+    }
+
+    pub fn with_strategy(strategy: LoadStrategy) -> Result<Self, AssetError> {
+        match strategy {
+            LoadStrategy::Reader => Self::from_source(&mut ReaderSource),
+            LoadStrategy::ReaderSimd => Self::from_source(&mut SimdReaderSource),
+            LoadStrategy::AllocBuffInOneGo => Self::from_source(&mut AllocBuffSource::new()),
+            LoadStrategy::SimdAllocBuffInOneGo => Self::from_source(&mut SimdAllocBuffSource::new()),
+            LoadStrategy::IncludeStr => Self::from_source(&mut IncludeStrSource),
+            LoadStrategy::ReadString => Self::from_source(&mut ReadStringSource),
+            LoadStrategy::ParallelReader => load_parallel::<ReaderSource>(),
+            LoadStrategy::ParallelReaderSimd => load_parallel::<SimdReaderSource>(),
+            LoadStrategy::Mmap => Self::from_source(&mut MmapSource),
+        }
+    }
+
+    pub fn from_source<S: AssetSource>(source: &mut S) -> Result<Self, AssetError> {
+        let skill_buff_data = source.load("SkillBuff.json")?;
+        let skill_data = source.load("Skill.json")?;
+        let skill_effect_data = source.load("SkillEffect.json")?;
+        let stat_type_map = source.load("StatType.json")?;
+
+        Ok(Self {
+            combat_effect_data: source.load("CombatEffect.json")?,
+            engraving_data: source.load("Ability.json")?,
+            buff_flags: BuffFlagIndex::build(&skill_buff_data, &stat_type_map),
+            skill_index: SkillIndex::build(&skill_data, &skill_buff_data, &skill_effect_data),
+            skill_buff_data,
+            skill_data,
+            skill_effect_data,
+            stat_type_map,
+            esther_data: source.load("Esther.json")?,
+            npc_data: source.load("Npc.json")?,
+            gem_skill_groups: source.load("GemSkillGroup.json")?,
+            raid_catalog: source.load("encounters.json")?,
+            class_registry: source.load("PCData.json")?,
+            item_sets: source.load("ItemSet.json")?,
+            support_buff_groups: SupportBuffGroups::load(source)?,
+        })
+    }
+
+    pub fn stat_resolver(&self) -> StatResolver<'_> {
+        StatResolver::new(&self.stat_type_map)
+    }
+
+    pub fn attributor(&self) -> Attributor<'_> {
+        Attributor::new(self)
+    }
+
+    /// Buffs that have every flag in `flags`, see [`BuffFlagIndex`].
+    pub fn buffs_with_flags(&self, flags: StatusEffectBuffTypeFlags) -> impl Iterator<Item = &SkillBuffData> {
+        self.buff_flags
+            .buffs_with_flags(flags)
+            .filter_map(|buff_id| self.skill_buff_data.get(&buff_id))
+    }
+}
@@ -0,0 +1,122 @@
+use crate::assets::AssetPreloader;
+use crate::models::*;
+use crate::support_buffs::SupportBuffKind;
+
+/// Which status effects were active on one hit and what they count as.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HitAttribution {
+    /// Known buffs on the source, sorted.
+    pub buffed_by: Vec<u32>,
+    /// Known debuffs on the target, sorted.
+    pub debuffed_by: Vec<u32>,
+    pub support: bool,
+    pub identity: bool,
+    pub brand: bool,
+    pub hat: bool,
+}
+
+impl HitAttribution {
+    pub fn apply_to_skill(&self, skill: &mut Skill, damage: i64) {
+        for buff_id in &self.buffed_by {
+            *skill.buffed_by.entry(*buff_id).or_default() += damage;
+        }
+        for debuff_id in &self.debuffed_by {
+            *skill.debuffed_by.entry(*debuff_id).or_default() += damage;
+        }
+
+        skill.buffed_by_support += self.support as i64 * damage;
+        skill.buffed_by_identity += self.identity as i64 * damage;
+        skill.debuffed_by_support += self.brand as i64 * damage;
+        skill.buffed_by_hat += self.hat as i64 * damage;
+    }
+
+    pub fn apply_to_stats(&self, stats: &mut DamageStats, damage: i64) {
+        for buff_id in &self.buffed_by {
+            *stats.buffed_by.entry(*buff_id).or_default() += damage;
+        }
+        for debuff_id in &self.debuffed_by {
+            *stats.debuffed_by.entry(*debuff_id).or_default() += damage;
+        }
+
+        stats.buffed_by_support += self.support as i64 * damage;
+        stats.buffed_by_identity += self.identity as i64 * damage;
+        stats.debuffed_by_support += self.brand as i64 * damage;
+        stats.buffed_by_hat += self.hat as i64 * damage;
+    }
+
+    pub fn apply_to_hit(&self, hit: &mut SkillHit) {
+        hit.buffed_by.clone_from(&self.buffed_by);
+        hit.debuffed_by.clone_from(&self.debuffed_by);
+    }
+}
+
+/// Classifies the status effects active at hit time against `SkillBuff.json` and `Skill.json`.
+#[derive(Clone, Copy)]
+pub struct Attributor<'a> {
+    assets: &'a AssetPreloader,
+}
+
+impl<'a> Attributor<'a> {
+    /// Brands and hyper-awakening buffs count for the classes of `assets.support_buff_groups`, see
+    /// [`SupportBuffGroups::support_classes`](crate::support_buffs::SupportBuffGroups::support_classes).
+    pub fn new(assets: &'a AssetPreloader) -> Self {
+        Self { assets }
+    }
+
+    fn source_skill(&self, buff: &SkillBuffData) -> Option<&'a SkillData> {
+        let skill_id = buff.source_skills.as_ref()?.first()?;
+        self.assets.skill_data.get(skill_id)
+    }
+
+    fn is_support(&self, skill: &SkillData) -> bool {
+        self.assets.support_buff_groups.is_support_class(skill.class_id)
+    }
+
+    /// Groups listed in `SupportBuffGroup.json` first, brands and hyper-awakening buffs
+    /// that are not listed are recognised by their source skill. `None` for buffs that are
+    /// not a tracked support contribution and for ids `SkillBuff.json` does not list.
+    pub fn kind_of(&self, buff_id: u32) -> Option<SupportBuffKind> {
+        let buff = self.assets.skill_buff_data.get(&buff_id)?;
+
+        if let Some(kind) = self.assets.support_buff_groups.kind_of(buff.unique_group) {
+            return Some(kind);
+        }
+
+        if buff.category == StatusEffectCategory::Debuff {
+            let from_support = self.source_skill(buff).is_some_and(|skill| self.is_support(skill));
+            let flags = self.assets.buff_flags.get(buff_id).unwrap_or(StatusEffectBuffTypeFlags::NONE);
+            let amplifies = flags.contains(StatusEffectBuffTypeFlags::DMG);
+            return (from_support && amplifies).then_some(SupportBuffKind::Brand);
+        }
+
+        let from_support_hat = |skill: &SkillData| skill.is_hyper_awakening && self.is_support(skill);
+        let hyper_awakening =
+            buff.target == SkillBuffTarget::Party && self.source_skill(buff).is_some_and(from_support_hat);
+        hyper_awakening.then_some(SupportBuffKind::HyperAwakening)
+    }
+
+    /// Attribution of a hit landed while `buffs` were on the source and `debuffs` on the target.
+    /// Ids `SkillBuff.json` does not list are left out.
+    pub fn attribute(&self, buffs: &[u32], debuffs: &[u32]) -> HitAttribution {
+        let known = |ids: &[u32]| {
+            let mut ids: Vec<u32> =
+                ids.iter().copied().filter(|id| self.assets.skill_buff_data.contains_key(id)).collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids
+        };
+
+        let (buffed_by, debuffed_by) = (known(buffs), known(debuffs));
+        let any_buff = |kind| buffed_by.iter().any(|id| self.kind_of(*id) == Some(kind));
+
+        HitAttribution {
+            support: any_buff(SupportBuffKind::AttackPower),
+            identity: any_buff(SupportBuffKind::Identity),
+            hat: any_buff(SupportBuffKind::HyperAwakening),
+            // brands only count on the target
+            brand: debuffed_by.iter().any(|id| self.kind_of(*id) == Some(SupportBuffKind::Brand)),
+            buffed_by,
+            debuffed_by,
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::{panic, sync::{Arc, OnceLock}, thread::{self, JoinHandle}};
+use hashbrown::HashMap;
+
+use crate::assets::{AssetPreloader, AssetSource};
+use crate::buff_flags::BuffFlagIndex;
+use crate::deser_reader::ReaderSource;
+use crate::classes::ClassRegistry;
+use crate::gem_skills::GemSkillGroupRegistry;
+use crate::error::AssetError;
+use crate::item_sets::ItemSetRegistry;
+use crate::raids::RaidCatalog;
+use crate::skill_index::SkillIndex;
+use crate::support_buffs::SupportBuffGroups;
+use crate::models::*;
+
+/// Tables filled in one by one by the background thread.
+#[derive(Default)]
+pub struct AssetTables {
+    combat_effect_data: OnceLock<HashMap<i32, CombatEffectData>>,
+    engraving_data: OnceLock<HashMap<u32, EngravingData>>,
+    skill_buff_data: OnceLock<HashMap<u32, SkillBuffData>>,
+    buff_flags: OnceLock<BuffFlagIndex>,
+    skill_data: OnceLock<HashMap<u32, SkillData>>,
+    skill_effect_data: OnceLock<HashMap<u32, SkillEffectData>>,
+    skill_index: OnceLock<SkillIndex>,
+    support_buff_groups: OnceLock<SupportBuffGroups>,
+    stat_type_map: OnceLock<HashMap<String, u32>>,
+    esther_data: OnceLock<Vec<Esther>>,
+    npc_data: OnceLock<HashMap<u32, Npc>>,
+    gem_skill_groups: OnceLock<GemSkillGroupRegistry>,
+    raid_catalog: OnceLock<RaidCatalog>,
+    class_registry: OnceLock<ClassRegistry>,
+    item_sets: OnceLock<ItemSetRegistry>,
+}
+
+impl AssetTables {
+    pub fn combat_effect_data(&self) -> Option<&HashMap<i32, CombatEffectData>> {
+        self.combat_effect_data.get()
+    }
+
+    pub fn engraving_data(&self) -> Option<&HashMap<u32, EngravingData>> {
+        self.engraving_data.get()
+    }
+
+    pub fn skill_buff_data(&self) -> Option<&HashMap<u32, SkillBuffData>> {
+        self.skill_buff_data.get()
+    }
+
+    pub fn buff_flags(&self) -> Option<&BuffFlagIndex> {
+        self.buff_flags.get()
+    }
+
+    pub fn skill_data(&self) -> Option<&HashMap<u32, SkillData>> {
+        self.skill_data.get()
+    }
+
+    pub fn skill_effect_data(&self) -> Option<&HashMap<u32, SkillEffectData>> {
+        self.skill_effect_data.get()
+    }
+
+    pub fn skill_index(&self) -> Option<&SkillIndex> {
+        self.skill_index.get()
+    }
+
+    pub fn support_buff_groups(&self) -> Option<&SupportBuffGroups> {
+        self.support_buff_groups.get()
+    }
+
+    pub fn stat_type_map(&self) -> Option<&HashMap<String, u32>> {
+        self.stat_type_map.get()
+    }
+
+    pub fn esther_data(&self) -> Option<&Vec<Esther>> {
+        self.esther_data.get()
+    }
+
+    pub fn npc_data(&self) -> Option<&HashMap<u32, Npc>> {
+        self.npc_data.get()
+    }
+
+    pub fn gem_skill_groups(&self) -> Option<&GemSkillGroupRegistry> {
+        self.gem_skill_groups.get()
+    }
+
+    pub fn raid_catalog(&self) -> Option<&RaidCatalog> {
+        self.raid_catalog.get()
+    }
+
+    pub fn class_registry(&self) -> Option<&ClassRegistry> {
+        self.class_registry.get()
+    }
+
+    pub fn item_sets(&self) -> Option<&ItemSetRegistry> {
+        self.item_sets.get()
+    }
+
+    // skill tables first, they are what the meter needs to attribute the first hits
+    fn load<S: AssetSource>(&self, source: &mut S) -> Result<(), AssetError> {
+        let _ = self.skill_data.set(source.load("Skill.json")?);
+        let _ = self.skill_buff_data.set(source.load("SkillBuff.json")?);
+        let _ = self.skill_effect_data.set(source.load("SkillEffect.json")?);
+        if let (Some(skill_data), Some(skill_buff_data), Some(skill_effect_data)) =
+            (self.skill_data.get(), self.skill_buff_data.get(), self.skill_effect_data.get())
+        {
+            let _ = self.skill_index.set(SkillIndex::build(skill_data, skill_buff_data, skill_effect_data));
+        }
+        let _ = self.support_buff_groups.set(SupportBuffGroups::load(source)?);
+        let _ = self.combat_effect_data.set(source.load("CombatEffect.json")?);
+        let _ = self.engraving_data.set(source.load("Ability.json")?);
+        let _ = self.stat_type_map.set(source.load("StatType.json")?);
+        if let (Some(skill_buff_data), Some(stat_type_map)) = (self.skill_buff_data.get(), self.stat_type_map.get()) {
+            let _ = self.buff_flags.set(BuffFlagIndex::build(skill_buff_data, stat_type_map));
+        }
+        let _ = self.esther_data.set(source.load("Esther.json")?);
+        let _ = self.npc_data.set(source.load("Npc.json")?);
+        let _ = self.gem_skill_groups.set(source.load("GemSkillGroup.json")?);
+        let _ = self.raid_catalog.set(source.load("encounters.json")?);
+        let _ = self.class_registry.set(source.load("PCData.json")?);
+        let _ = self.item_sets.set(source.load("ItemSet.json")?);
+        Ok(())
+    }
+
+    fn into_preloader(self) -> Option<AssetPreloader> {
+        Some(AssetPreloader {
+            combat_effect_data: self.combat_effect_data.into_inner()?,
+            engraving_data: self.engraving_data.into_inner()?,
+            skill_buff_data: self.skill_buff_data.into_inner()?,
+            buff_flags: self.buff_flags.into_inner()?,
+            skill_data: self.skill_data.into_inner()?,
+            skill_effect_data: self.skill_effect_data.into_inner()?,
+            skill_index: self.skill_index.into_inner()?,
+            support_buff_groups: self.support_buff_groups.into_inner()?,
+            stat_type_map: self.stat_type_map.into_inner()?,
+            esther_data: self.esther_data.into_inner()?,
+            npc_data: self.npc_data.into_inner()?,
+            gem_skill_groups: self.gem_skill_groups.into_inner()?,
+            raid_catalog: self.raid_catalog.into_inner()?,
+            class_registry: self.class_registry.into_inner()?,
+            item_sets: self.item_sets.into_inner()?,
+        })
+    }
+}
+
+/// Loads the assets on a background thread so the caller can carry on,
+/// individual tables can be read through [`BackgroundPreloader::tables`] as soon as they land.
+pub struct BackgroundPreloader {
+    tables: Arc<AssetTables>,
+    handle: JoinHandle<Result<(), AssetError>>,
+}
+
+impl BackgroundPreloader {
+    pub fn new() -> Self {
+        Self::spawn(ReaderSource)
+    }
+
+    pub fn spawn<S: AssetSource + Send + 'static>(mut source: S) -> Self {
+        let tables = Arc::new(AssetTables::default());
+        let handle = thread::spawn({
+            let tables = tables.clone();
+            move || tables.load(&mut source)
+        });
+
+        Self { tables, handle }
+    }
+
+    pub fn tables(&self) -> &AssetTables {
+        &self.tables
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Returns the loaded assets if the thread is done, hands `self` back otherwise.
+    pub fn try_get(self) -> Result<Result<AssetPreloader, AssetError>, Self> {
+        if !self.is_finished() {
+            return Err(self);
+        }
+
+        Ok(self.wait())
+    }
+
+    pub fn wait(self) -> Result<AssetPreloader, AssetError> {
+        self.handle.join().unwrap_or_else(|err| panic::resume_unwind(err))?;
+
+        // the thread dropped its handle to the tables when it returned
+        let tables = Arc::into_inner(self.tables).expect("background loader still holds the tables");
+        Ok(tables.into_preloader().expect("background loader finished without filling every table"))
+    }
+}
+
+impl Default for BackgroundPreloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,127 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::models::*;
+use crate::stats::{StatKind, StatResolver};
+
+/// Flags a buff gets from its `type` alone.
+fn buff_type_flags(buff_type: &SkillBuffType) -> StatusEffectBuffTypeFlags {
+    match buff_type {
+        SkillBuffType::WeakenDefense
+        | SkillBuffType::WeakenResistance
+        | SkillBuffType::SkillDamageAmplify
+        | SkillBuffType::BeattackedDamageAmplify
+        | SkillBuffType::SkillDamageAmplifyAttack
+        | SkillBuffType::DirectionalAttackAmplify
+        | SkillBuffType::InstantStatAmplify
+        | SkillBuffType::InstantStatAmplifyByContents
+        | SkillBuffType::AttackPowerAmplify => StatusEffectBuffTypeFlags::DMG,
+        SkillBuffType::MoveSpeedDown | SkillBuffType::AllSpeedDown => StatusEffectBuffTypeFlags::MOVESPEED,
+        SkillBuffType::ResetCooldown => StatusEffectBuffTypeFlags::COOLDOWN,
+        SkillBuffType::ChangeAiPoint | SkillBuffType::AiPointAmplify => StatusEffectBuffTypeFlags::STAGGER,
+        SkillBuffType::IncreaseIdentityGauge => StatusEffectBuffTypeFlags::RESOURCE,
+        SkillBuffType::Shield => StatusEffectBuffTypeFlags::SHIELD,
+        SkillBuffType::Stat | SkillBuffType::Unknown(_) => StatusEffectBuffTypeFlags::NONE,
+    }
+}
+
+/// Flags for one stat change. Offensive stats are `DMG` on buffs and `DEFENSE` on debuffs, where they
+/// weaken the target's damage, defensive stats the other way round.
+fn stat_flags(kind: StatKind, category: &StatusEffectCategory) -> StatusEffectBuffTypeFlags {
+    let on_debuff = *category == StatusEffectCategory::Debuff;
+
+    match kind {
+        StatKind::CritRate => StatusEffectBuffTypeFlags::CRIT,
+        StatKind::AttackSpeed => StatusEffectBuffTypeFlags::ATKSPEED,
+        StatKind::Swiftness => StatusEffectBuffTypeFlags::ATKSPEED | StatusEffectBuffTypeFlags::COOLDOWN,
+        StatKind::MoveSpeed => StatusEffectBuffTypeFlags::MOVESPEED,
+        StatKind::Cooldown => StatusEffectBuffTypeFlags::COOLDOWN,
+        StatKind::Hp => StatusEffectBuffTypeFlags::HP,
+        StatKind::Resource => StatusEffectBuffTypeFlags::RESOURCE,
+        StatKind::Stagger => StatusEffectBuffTypeFlags::STAGGER,
+        StatKind::CritDamage
+        | StatKind::AttackPower
+        | StatKind::AttackPowerRate
+        | StatKind::DamageRate
+        | StatKind::Penetration => {
+            if on_debuff { StatusEffectBuffTypeFlags::DEFENSE } else { StatusEffectBuffTypeFlags::DMG }
+        }
+        StatKind::Defense => {
+            if on_debuff { StatusEffectBuffTypeFlags::DMG } else { StatusEffectBuffTypeFlags::DEFENSE }
+        }
+        StatKind::Other => StatusEffectBuffTypeFlags::NONE,
+    }
+}
+
+/// Classifies a buff from its type, its `status_effect_values` and the passive options of every level.
+pub fn classify_buff(buff: &SkillBuffData, resolver: &StatResolver<'_>) -> StatusEffectBuffTypeFlags {
+    let mut flags = buff_type_flags(&buff.buff_type);
+
+    // amplify buffs carry their amount in the values, all zeroes means the buff does nothing by itself
+    if flags == StatusEffectBuffTypeFlags::DMG
+        && buff.status_effect_values.as_ref().is_some_and(|values| values.iter().all(|value| *value == 0))
+    {
+        flags = StatusEffectBuffTypeFlags::NONE;
+    }
+
+    let options = buff.per_level_data.values().flat_map(|data| data.passive_options.iter());
+    for delta in options.filter_map(|option| resolver.option_delta(option)) {
+        flags |= stat_flags(delta.kind, &buff.category);
+    }
+
+    flags
+}
+
+/// [`StatusEffectBuffTypeFlags`] of every `SkillBuff.json` entry, built alongside the buff table.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "HashMap<u32, u32>", into = "HashMap<u32, u32>")]
+pub struct BuffFlagIndex {
+    flags: HashMap<u32, StatusEffectBuffTypeFlags>,
+}
+
+impl From<HashMap<u32, u32>> for BuffFlagIndex {
+    fn from(bits: HashMap<u32, u32>) -> Self {
+        let flags = bits
+            .into_iter()
+            .map(|(id, bits)| (id, StatusEffectBuffTypeFlags::from_bits_truncate(bits)))
+            .collect();
+        Self { flags }
+    }
+}
+
+impl From<BuffFlagIndex> for HashMap<u32, u32> {
+    fn from(index: BuffFlagIndex) -> Self {
+        index.flags.into_iter().map(|(id, flags)| (id, flags.bits())).collect()
+    }
+}
+
+impl BuffFlagIndex {
+    pub fn build(skill_buff_data: &HashMap<u32, SkillBuffData>, stat_type_map: &HashMap<String, u32>) -> Self {
+        let resolver = StatResolver::new(stat_type_map);
+        let flags = skill_buff_data
+            .iter()
+            .map(|(id, buff)| (*id, classify_buff(buff, &resolver)))
+            .collect();
+        Self { flags }
+    }
+
+    pub fn get(&self, buff_id: u32) -> Option<StatusEffectBuffTypeFlags> {
+        self.flags.get(&buff_id).copied()
+    }
+
+    /// Ids of the buffs that have every flag in `flags`, in no particular order.
+    pub fn buffs_with_flags(&self, flags: StatusEffectBuffTypeFlags) -> impl Iterator<Item = u32> + '_ {
+        self.flags
+            .iter()
+            .filter(move |(_, buff_flags)| buff_flags.contains(flags))
+            .map(|(id, _)| *id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.flags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
+}
@@ -0,0 +1,164 @@
+use std::{fs, path::{Path, PathBuf}, time::UNIX_EPOCH};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::assets::{AssetPreloader, LoadStrategy, ASSET_DIR, ASSET_FILES};
+use crate::support_buffs::SUPPORT_BUFF_OVERRIDES;
+use crate::error::AssetError;
+
+/// Bump whenever a model changes shape, old caches are then rebuilt instead of misread.
+const CACHE_VERSION: u32 = 10;
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct SourceStamp {
+    name: String,
+    len: u64,
+    modified_secs: u64,
+    modified_nanos: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheHeader {
+    version: u32,
+    crate_version: String,
+    // the feature changes the shape of `EngravingData`
+    slim_engravings: bool,
+    sources: Vec<SourceStamp>,
+}
+
+impl SourceStamp {
+    fn of(name: &str, metadata: fs::Metadata) -> Self {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+
+        Self {
+            name: name.to_string(),
+            len: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+        }
+    }
+}
+
+impl CacheHeader {
+    fn current(asset_dir: &Path) -> Result<Self, AssetError> {
+        let mut sources = ASSET_FILES
+            .iter()
+            .map(|name| {
+                let path = asset_dir.join(name);
+                let metadata =
+                    fs::metadata(&path).map_err(|err| AssetError::io(&path.display().to_string(), err))?;
+                Ok(SourceStamp::of(name, metadata))
+            })
+            .collect::<Result<Vec<_>, AssetError>>()?;
+
+        // the user overrides are optional, adding, editing or removing them all invalidate the cache
+        if let Ok(metadata) = fs::metadata(asset_dir.join(SUPPORT_BUFF_OVERRIDES)) {
+            sources.push(SourceStamp::of(SUPPORT_BUFF_OVERRIDES, metadata));
+        }
+
+        Ok(Self {
+            version: CACHE_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            slim_engravings: cfg!(feature = "slim-engravings"),
+            sources,
+        })
+    }
+}
+
+/// On-disk postcard copy of a fully built [`AssetPreloader`],
+/// stamped with the size and mtime of every source file it was built from.
+pub struct AssetCache {
+    path: PathBuf,
+    asset_dir: PathBuf,
+}
+
+impl AssetCache {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), asset_dir: ASSET_DIR.into() }
+    }
+
+    /// Stamps the source files in `asset_dir` instead of [`ASSET_DIR`].
+    pub fn with_asset_dir(mut self, asset_dir: impl Into<PathBuf>) -> Self {
+        self.asset_dir = asset_dir.into();
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn asset_dir(&self) -> &Path {
+        &self.asset_dir
+    }
+
+    /// Reads the cache if it is still up to date, otherwise parses the JSON with `strategy` and rewrites it.
+    ///
+    /// The strategies read [`ASSET_DIR`], use [`load_or_build_with`](Self::load_or_build_with)
+    /// together with [`with_asset_dir`](Self::with_asset_dir).
+    pub fn load_or_build(&self, strategy: LoadStrategy) -> Result<AssetPreloader, AssetError> {
+        self.load_or_build_with(|| AssetPreloader::with_strategy(strategy))
+    }
+
+    /// Like [`load_or_build`](Self::load_or_build), `build` has to read the files in the asset directory.
+    pub fn load_or_build_with<F>(&self, build: F) -> Result<AssetPreloader, AssetError>
+    where
+        F: FnOnce() -> Result<AssetPreloader, AssetError>,
+    {
+        let header = CacheHeader::current(&self.asset_dir)?;
+
+        if let Some(assets) = self.read(&header) {
+            return Ok(assets);
+        }
+
+        let assets = build()?;
+
+        if let Err(err) = self.write(&header, &assets) {
+            warn!("{err}");
+        }
+
+        Ok(assets)
+    }
+
+    /// Returns the cached assets, or `None` when the cache is missing, corrupt or stale.
+    pub fn load(&self) -> Option<AssetPreloader> {
+        let header = CacheHeader::current(&self.asset_dir).ok()?;
+        self.read(&header)
+    }
+
+    pub fn store(&self, assets: &AssetPreloader) -> Result<(), AssetError> {
+        self.write(&CacheHeader::current(&self.asset_dir)?, assets)
+    }
+
+    fn read(&self, expected: &CacheHeader) -> Option<AssetPreloader> {
+        let bytes = fs::read(&self.path).ok()?;
+        let (header, payload) = postcard::take_from_bytes::<CacheHeader>(&bytes).ok()?;
+
+        if header != *expected {
+            return None;
+        }
+
+        postcard::from_bytes(payload).ok()
+    }
+
+    fn write(&self, header: &CacheHeader, assets: &AssetPreloader) -> Result<(), AssetError> {
+        let path = self.path.display().to_string();
+        let bytes = postcard::to_stdvec(&(header, assets)).map_err(|err| AssetError::Cache {
+            path: path.clone(),
+            message: err.to_string(),
+        })?;
+
+        // write next to the cache and rename so a crash never leaves a half written file behind,
+        // appending keeps the temp file distinct even when the cache itself ends in `.tmp`
+        let mut file_name = self.path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".tmp");
+        let tmp_path = self.path.with_file_name(file_name);
+        // not `AssetError::io`, a missing cache directory is not a missing asset file
+        fs::write(&tmp_path, bytes)
+            .map_err(|source| AssetError::Io { path: tmp_path.display().to_string(), source })?;
+        fs::rename(&tmp_path, &self.path).map_err(|source| AssetError::Io { path, source })
+    }
+}
@@ -0,0 +1,92 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use compact_str::CompactString;
+
+use crate::support_buffs::SupportBuffGroups;
+
+/// `PCData.json` as written, class id to class name.
+pub type ClassNames = HashMap<u32, CompactString>;
+
+/// Class id <-> name lookups over `PCData.json`.
+///
+/// Ids follow a `xy1` scheme: ids ending in `1` are base classes
+/// (101 "Warrior (Male)") and the ids up to the next ten are the advanced
+/// classes built on it (102 Berserker .. 105 Paladin).
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "ClassNames", into = "ClassNames")]
+pub struct ClassRegistry {
+    names: ClassNames,
+    ids: HashMap<CompactString, u32>,
+}
+
+impl From<ClassNames> for ClassRegistry {
+    fn from(mut names: ClassNames) -> Self {
+        // 0 is the game's "ENUMNULL" placeholder, not a class
+        names.remove(&0);
+        let ids = names.iter().map(|(id, name)| (name.clone(), *id)).collect();
+        Self { names, ids }
+    }
+}
+
+impl From<ClassRegistry> for ClassNames {
+    fn from(registry: ClassRegistry) -> Self {
+        registry.names
+    }
+}
+
+impl ClassRegistry {
+    pub fn name_of(&self, class_id: u32) -> Option<&str> {
+        self.names.get(&class_id).map(CompactString::as_str)
+    }
+
+    pub fn id_of(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn contains(&self, class_id: u32) -> bool {
+        self.names.contains_key(&class_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names.iter().map(|(id, name)| (*id, name.as_str()))
+    }
+
+    pub fn is_base_class(&self, class_id: u32) -> bool {
+        class_id % 10 == 1 && self.contains(class_id)
+    }
+
+    /// The base class an advanced class is built on, `None` for base classes and unknown ids.
+    pub fn base_class_of(&self, class_id: u32) -> Option<u32> {
+        if class_id % 10 == 1 || !self.contains(class_id) {
+            return None;
+        }
+
+        let base = class_id - class_id % 10 + 1;
+        self.contains(base).then_some(base)
+    }
+
+    /// Advanced classes of `base_class_id` in id order.
+    pub fn advanced_classes_of(&self, base_class_id: u32) -> Vec<u32> {
+        if !self.is_base_class(base_class_id) {
+            return Vec::new();
+        }
+
+        (base_class_id + 1..=base_class_id + 8)
+            .filter(|class_id| self.contains(*class_id))
+            .collect()
+    }
+
+    /// Whether `class_id` is a known class with support buff groups in `SupportBuffGroup.json`
+    /// or the user overrides.
+    pub fn is_support(&self, class_id: u32, support_buff_groups: &SupportBuffGroups) -> bool {
+        self.contains(class_id) && support_buff_groups.is_support_class(class_id)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::models::*;
+
+/// A [`CombatEffectCondition`] with its `arg` read for the condition type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombatEffectConditionKind<'a> {
+    /// The hit struck exactly `count` targets.
+    TargetCount { count: i32 },
+    CurrentSkill { skill_id: u32 },
+    PcSkill,
+    SkillIdentityCategory { category: i32 },
+    AbnormalMoveImmune,
+    AbnormalMoveAll,
+    AbnormalMove,
+    AbnormalStatus,
+    /// The actor is below `percent` of their max hp.
+    HpLess { percent: i32 },
+    NpcGradeLess { grade: i32 },
+    NpcGradeGreater { grade: i32 },
+    IdentityStance { stance: i32 },
+    /// Bit `n - 1` of `mask` allows the [`HitOption`] with discriminant `n`, back attacks are bit 0.
+    DirectionalAttack { mask: i32 },
+    Critical,
+    Unknown { condition_type: &'a str, arg: i32 },
+}
+
+/// A [`CombatEffectAction`] with its `args` read for the action type, rates are in 1/10000.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombatEffectActionKind<'a> {
+    ModifyDamage { rate: i32 },
+    ModifyFinalDamage { rate: i32 },
+    ModifyCriticalRatio { rate: i32 },
+    ModifyCriticalMultiplier { rate: i32 },
+    ModifyPenetration { rate: i32 },
+    ModifyPenetrationWhenCritical { rate: i32 },
+    ModifyDamageShieldMultiplier { rate: i32 },
+    Unknown { action_type: &'a str, args: &'a [i32] },
+}
+
+impl CombatEffectCondition {
+    pub fn condition(&self) -> CombatEffectConditionKind<'_> {
+        use CombatEffectConditionKind as Kind;
+
+        let arg = self.arg;
+        match &self.condition_type {
+            CombatEffectConditionType::TargetCount => Kind::TargetCount { count: arg },
+            CombatEffectConditionType::CurrentSkill => Kind::CurrentSkill { skill_id: arg as u32 },
+            CombatEffectConditionType::PcSkill => Kind::PcSkill,
+            CombatEffectConditionType::SkillIdentityCategory => Kind::SkillIdentityCategory { category: arg },
+            CombatEffectConditionType::AbnormalMoveImmune => Kind::AbnormalMoveImmune,
+            CombatEffectConditionType::AbnormalMoveAll => Kind::AbnormalMoveAll,
+            CombatEffectConditionType::AbnormalMove => Kind::AbnormalMove,
+            CombatEffectConditionType::AbnormalStatus => Kind::AbnormalStatus,
+            CombatEffectConditionType::HpLess => Kind::HpLess { percent: arg },
+            CombatEffectConditionType::NpcGradeLess => Kind::NpcGradeLess { grade: arg },
+            CombatEffectConditionType::NpcGradeGreater => Kind::NpcGradeGreater { grade: arg },
+            CombatEffectConditionType::IdentityStance => Kind::IdentityStance { stance: arg },
+            CombatEffectConditionType::DirectionalAttack => Kind::DirectionalAttack { mask: arg },
+            CombatEffectConditionType::Critical => Kind::Critical,
+            CombatEffectConditionType::Unknown(condition_type) => Kind::Unknown { condition_type, arg },
+        }
+    }
+
+    /// Conditions the meter cannot check, unknown types or actors, never hold.
+    pub fn holds(&self, hit: &HitContext) -> bool {
+        use CombatEffectConditionKind as Kind;
+
+        let actor = hit.actor(&self.actor_type);
+        match self.condition() {
+            Kind::TargetCount { count } => hit.target_count == count,
+            Kind::CurrentSkill { skill_id } => hit.skill_id == skill_id,
+            Kind::PcSkill => hit.is_pc_skill,
+            Kind::SkillIdentityCategory { category } => hit.skill_identity_category == Some(category),
+            Kind::AbnormalMoveImmune => actor.is_some_and(|actor| actor.abnormal_move_immune),
+            Kind::AbnormalMoveAll | Kind::AbnormalMove => actor.is_some_and(|actor| actor.abnormal_move),
+            Kind::AbnormalStatus => actor.is_some_and(|actor| actor.abnormal_status),
+            Kind::HpLess { percent } => actor.is_some_and(|actor| actor.hp_percent < percent as f32),
+            Kind::NpcGradeLess { grade } => {
+                actor.and_then(|actor| actor.npc_grade).is_some_and(|npc| npc < grade)
+            }
+            Kind::NpcGradeGreater { grade } => {
+                actor.and_then(|actor| actor.npc_grade).is_some_and(|npc| npc > grade)
+            }
+            Kind::IdentityStance { stance } => actor.is_some_and(|actor| actor.identity_stance == stance),
+            Kind::DirectionalAttack { mask } => match hit.hit_option {
+                HitOption::BACK_ATTACK => mask & 1 != 0,
+                HitOption::FRONTAL_ATTACK => mask & (1 << 1) != 0,
+                HitOption::FLANK_ATTACK => mask & (1 << 2) != 0,
+                _ => false,
+            },
+            Kind::Critical => hit.hit_flag.is_crit(),
+            Kind::Unknown { .. } => false,
+        }
+    }
+}
+
+impl CombatEffectAction {
+    pub fn action(&self) -> CombatEffectActionKind<'_> {
+        use CombatEffectActionKind as Kind;
+
+        let rate = self.args.first().copied().unwrap_or_default();
+        match &self.action_type {
+            CombatEffectActionType::ModifyDamage => Kind::ModifyDamage { rate },
+            CombatEffectActionType::ModifyFinalDamage => Kind::ModifyFinalDamage { rate },
+            CombatEffectActionType::ModifyCriticalRatio => Kind::ModifyCriticalRatio { rate },
+            CombatEffectActionType::ModifyCriticalMultiplier => Kind::ModifyCriticalMultiplier { rate },
+            CombatEffectActionType::ModifyPenetration => Kind::ModifyPenetration { rate },
+            CombatEffectActionType::ModifyPenetrationWhenCritical => Kind::ModifyPenetrationWhenCritical { rate },
+            CombatEffectActionType::ModifyDamageShieldMultiplier => Kind::ModifyDamageShieldMultiplier { rate },
+            CombatEffectActionType::Unknown(action_type) => Kind::Unknown { action_type, args: &self.args },
+        }
+    }
+}
+
+/// State of one side of a hit that conditions can ask about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorState {
+    pub hp_percent: f32,
+    pub identity_stance: i32,
+    /// The game's grade ordinal for npcs, `None` for players.
+    pub npc_grade: Option<i32>,
+    pub abnormal_move: bool,
+    pub abnormal_move_immune: bool,
+    pub abnormal_status: bool,
+}
+
+impl Default for ActorState {
+    fn default() -> Self {
+        Self {
+            hp_percent: 100.0,
+            identity_stance: 0,
+            npc_grade: None,
+            abnormal_move: false,
+            abnormal_move_immune: false,
+            abnormal_status: false,
+        }
+    }
+}
+
+/// Everything known about a hit when deciding which combat effects apply to it.
+#[derive(Debug)]
+pub struct HitContext {
+    pub skill_id: u32,
+    pub is_pc_skill: bool,
+    pub skill_identity_category: Option<i32>,
+    pub target_count: i32,
+    pub hit_flag: HitFlag,
+    pub hit_option: HitOption,
+    /// Whoever landed the hit, the `self` actor.
+    pub caster: ActorState,
+    pub target: ActorState,
+}
+
+impl Default for HitContext {
+    fn default() -> Self {
+        Self {
+            skill_id: 0,
+            is_pc_skill: false,
+            skill_identity_category: None,
+            target_count: 1,
+            hit_flag: HitFlag::NORMAL,
+            hit_option: HitOption::NONE,
+            caster: ActorState::default(),
+            target: ActorState::default(),
+        }
+    }
+}
+
+impl HitContext {
+    fn actor(&self, actor: &CombatEffectActor) -> Option<&ActorState> {
+        match actor {
+            CombatEffectActor::SelfActor | CombatEffectActor::Caster => Some(&self.caster),
+            CombatEffectActor::Target => Some(&self.target),
+            CombatEffectActor::Unknown(_) => None,
+        }
+    }
+}
+
+impl CombatEffectDetail {
+    /// Whether every condition holds for `hit`, `ratio` and `cooldown` are left to the caller.
+    pub fn fires(&self, hit: &HitContext) -> bool {
+        self.conditions.iter().all(|condition| condition.holds(hit))
+    }
+}
+
+impl CombatEffectData {
+    /// The effects whose conditions all hold for `hit`, in file order.
+    pub fn firing<'a>(&'a self, hit: &'a HitContext) -> impl Iterator<Item = &'a CombatEffectDetail> {
+        self.effects.iter().filter(|effect| effect.fires(hit))
+    }
+}
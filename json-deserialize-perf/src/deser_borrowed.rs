@@ -0,0 +1,100 @@
+use hashbrown::HashMap;
+use serde::Deserialize;
+
+use crate::assets::{asset_path, parse_json_str, AssetSource};
+use crate::deser_include_str::embedded;
+use crate::error::AssetError;
+use crate::models::{EngravingData, Npc, SkillBuffData, SkillData, SkillEffectData};
+use crate::models_ref::*;
+
+/// The files [`BorrowedAssets`] and [`OwnedAssets`] load.
+pub const BORROWED_FILES: [&str; 5] = ["Ability.json", "SkillBuff.json", "Skill.json", "SkillEffect.json", "Npc.json"];
+
+/// Owns the raw JSON the [`BorrowedAssets`] tables point into.
+pub struct AssetBuffers {
+    buffers: HashMap<&'static str, String>,
+}
+
+impl AssetBuffers {
+    pub fn read() -> Result<Self, AssetError> {
+        let buffers = BORROWED_FILES
+            .iter()
+            .map(|name| {
+                let path = asset_path(name);
+                let contents = std::fs::read_to_string(&path).map_err(|err| AssetError::io(&path, err))?;
+                Ok((*name, contents))
+            })
+            .collect::<Result<_, AssetError>>()?;
+
+        Ok(Self { buffers })
+    }
+
+    pub fn parse(&self) -> Result<BorrowedAssets<'_>, AssetError> {
+        BorrowedAssets::parse(|name| {
+            self.buffers
+                .get(name)
+                .map(String::as_str)
+                .ok_or_else(|| AssetError::MissingFile { path: asset_path(name) })
+        })
+    }
+}
+
+pub struct BorrowedAssets<'a> {
+    pub engraving_data: HashMap<u32, EngravingDataRef<'a>>,
+    pub skill_buff_data: HashMap<u32, SkillBuffDataRef<'a>>,
+    pub skill_data: HashMap<u32, SkillDataRef<'a>>,
+    pub skill_effect_data: HashMap<u32, SkillEffectDataRef<'a>>,
+    pub npc_data: HashMap<u32, NpcRef<'a>>,
+}
+
+impl BorrowedAssets<'static> {
+    /// Borrows from the files embedded with `include_str!`, which live for the whole program.
+    pub fn embedded() -> Result<Self, AssetError> {
+        Self::parse(|name| embedded(name).ok_or_else(|| AssetError::MissingFile { path: name.into() }))
+    }
+}
+
+impl<'a> BorrowedAssets<'a> {
+    /// Borrows from the buffer `buffer` hands out for each of [`BORROWED_FILES`].
+    pub fn parse<F>(buffer: F) -> Result<Self, AssetError>
+    where
+        F: Fn(&'static str) -> Result<&'a str, AssetError>,
+    {
+        Ok(Self {
+            engraving_data: table("Ability.json", &buffer)?,
+            skill_buff_data: table("SkillBuff.json", &buffer)?,
+            skill_data: table("Skill.json", &buffer)?,
+            skill_effect_data: table("SkillEffect.json", &buffer)?,
+            npc_data: table("Npc.json", &buffer)?,
+        })
+    }
+}
+
+fn table<'a, T, F>(name: &'static str, buffer: &F) -> Result<T, AssetError>
+where
+    T: Deserialize<'a>,
+    F: Fn(&'static str) -> Result<&'a str, AssetError>,
+{
+    parse_json_str(name, buffer(name)?)
+}
+
+/// The owned counterpart of [`BorrowedAssets`], the same [`BORROWED_FILES`] into the owned models.
+pub struct OwnedAssets {
+    pub engraving_data: HashMap<u32, EngravingData>,
+    pub skill_buff_data: HashMap<u32, SkillBuffData>,
+    pub skill_data: HashMap<u32, SkillData>,
+    pub skill_effect_data: HashMap<u32, SkillEffectData>,
+    pub npc_data: HashMap<u32, Npc>,
+}
+
+impl OwnedAssets {
+    pub fn load<S: AssetSource>(source: &mut S) -> Result<Self, AssetError> {
+        Ok(Self {
+            engraving_data: source.load("Ability.json")?,
+            skill_buff_data: source.load("SkillBuff.json")?,
+            skill_data: source.load("Skill.json")?,
+            skill_effect_data: source.load("SkillEffect.json")?,
+            npc_data: source.load("Npc.json")?,
+        })
+    }
+}
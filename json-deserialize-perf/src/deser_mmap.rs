@@ -0,0 +1,19 @@
+use std::fs::File;
+use memmap2::Mmap;
+use serde::de::DeserializeOwned;
+
+use crate::assets::{asset_path, parse_json_slice, AssetSource};
+use crate::error::AssetError;
+
+#[derive(Default)]
+pub struct MmapSource;
+
+impl AssetSource for MmapSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        let path = asset_path(name);
+        let file = File::open(&path).map_err(|err| AssetError::io(&path, err))?;
+        // SAFETY: the asset files are not expected to be modified while the meter starts up
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|err| AssetError::io(&path, err))?;
+        parse_json_slice(name, &mmap)
+    }
+}
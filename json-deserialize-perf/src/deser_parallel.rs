@@ -0,0 +1,58 @@
+use std::{panic, thread::{self, ScopedJoinHandle}};
+
+use crate::assets::{AssetPreloader, AssetSource};
+use crate::buff_flags::BuffFlagIndex;
+use crate::error::AssetError;
+use crate::skill_index::SkillIndex;
+use crate::support_buffs::SupportBuffGroups;
+
+fn join<T>(handle: ScopedJoinHandle<'_, Result<T, AssetError>>) -> Result<T, AssetError> {
+    handle.join().unwrap_or_else(|err| panic::resume_unwind(err))
+}
+
+/// Parses every file on its own scoped thread, each with a fresh `S`,
+/// and assembles the same tables as [`AssetPreloader::from_source`].
+pub fn load_parallel<S: AssetSource + Default>() -> Result<AssetPreloader, AssetError> {
+    thread::scope(|scope| {
+        let combat_effect_data = scope.spawn(|| S::default().load("CombatEffect.json"));
+        let engraving_data = scope.spawn(|| S::default().load("Ability.json"));
+        let skill_buff_data = scope.spawn(|| S::default().load("SkillBuff.json"));
+        let skill_data = scope.spawn(|| S::default().load("Skill.json"));
+        let skill_effect_data = scope.spawn(|| S::default().load("SkillEffect.json"));
+        let npc_data = scope.spawn(|| S::default().load("Npc.json"));
+
+        // the small files are not worth a thread each
+        let mut source = S::default();
+        let stat_type_map = source.load("StatType.json")?;
+        let esther_data = source.load("Esther.json")?;
+        let gem_skill_groups = source.load("GemSkillGroup.json")?;
+        let raid_catalog = source.load("encounters.json")?;
+        let class_registry = source.load("PCData.json")?;
+        let item_sets = source.load("ItemSet.json")?;
+        let support_buff_groups = SupportBuffGroups::load(&mut source)?;
+
+        let skill_buff_data = join(skill_buff_data)?;
+        let skill_data = join(skill_data)?;
+        let skill_effect_data = join(skill_effect_data)?;
+        let buff_flags = BuffFlagIndex::build(&skill_buff_data, &stat_type_map);
+        let skill_index = SkillIndex::build(&skill_data, &skill_buff_data, &skill_effect_data);
+
+        Ok(AssetPreloader {
+            combat_effect_data: join(combat_effect_data)?,
+            engraving_data: join(engraving_data)?,
+            skill_buff_data,
+            buff_flags,
+            skill_data,
+            skill_effect_data,
+            skill_index,
+            stat_type_map,
+            esther_data,
+            npc_data: join(npc_data)?,
+            gem_skill_groups,
+            raid_catalog,
+            class_registry,
+            item_sets,
+            support_buff_groups,
+        })
+    })
+}
@@ -0,0 +1,271 @@
+use compact_str::CompactString;
+use hashbrown::HashMap;
+
+use crate::attribution::HitAttribution;
+use crate::error::HitDecodeError;
+use crate::models::*;
+
+/// One hit as seen by the meter, `timestamp` in epoch milliseconds.
+#[derive(Debug)]
+pub struct DamageEvent {
+    pub source_id: u64,
+    pub target_id: u64,
+    pub damage: DamageData,
+    pub hit_flag: HitFlag,
+    pub hit_option: HitOption,
+    pub timestamp: i64,
+}
+
+impl DamageEvent {
+    /// An event whose flag and option come from `damage.modifier`.
+    pub fn decode(
+        source_id: u64,
+        target_id: u64,
+        damage: DamageData,
+        timestamp: i64,
+    ) -> Result<Self, HitDecodeError> {
+        let (hit_flag, hit_option) = damage.hit()?;
+        Ok(Self { source_id, target_id, damage, hit_flag, hit_option, timestamp })
+    }
+}
+
+/// Folds skill casts and damage events into an [`Encounter`].
+///
+/// Entities have to be registered before events naming them are ingested, events for unknown ids
+/// are dropped. Cast and hit times are relative to the earliest hit, casts seen before it are negative.
+///
+/// Events may arrive out of order. A hit goes to the latest cast of its skill started at or before it
+/// among the casts seen so far, and a hit older than `fight_start` moves the start back, which shifts
+/// every recorded time.
+#[derive(Debug, Default)]
+pub struct EncounterBuilder {
+    encounter: Encounter,
+    names: HashMap<u64, CompactString>,
+    // per (source, skill) damage of each cast in `skill_cast_log`, so `max_damage_cast` does not re-sum hits
+    cast_damage: HashMap<(u64, u32), Vec<i64>>,
+    // the timestamp relative times count from, the first cast until there is a hit, then `fight_start`
+    origin: i64,
+}
+
+impl EncounterBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_entity(&mut self, entity: EncounterEntity) {
+        self.names.insert(entity.id, entity.name.clone());
+        self.encounter.entities.insert(entity.name.clone(), entity);
+    }
+
+    pub fn encounter(&self) -> &Encounter {
+        &self.encounter
+    }
+
+    /// Records a cast of `skill_id`, returns `false` if the caster is not registered.
+    pub fn cast(&mut self, source_id: u64, skill_id: u32, timestamp: i64) -> bool {
+        let Some(name) = self.names.get(&source_id) else {
+            return false;
+        };
+        if self.origin == 0 {
+            self.origin = timestamp;
+        }
+        let relative = timestamp - self.origin;
+        let entity = self.encounter.entities.get_mut(name).expect("registered entity");
+
+        entity.skill_stats.casts += 1;
+        let index = start_cast(skill_entry(entity, skill_id), relative, timestamp);
+        self.cast_damage.entry((source_id, skill_id)).or_default().insert(index, 0);
+        true
+    }
+
+    /// Records a hit, returns `false` if it was dropped.
+    pub fn ingest(&mut self, event: DamageEvent) -> bool {
+        self.ingest_attributed(event, &HitAttribution::default())
+    }
+
+    /// Records a hit along with the buffs and debuffs it was landed under, see
+    /// [`Attributor::attribute`](crate::attribution::Attributor::attribute).
+    pub fn ingest_attributed(&mut self, event: DamageEvent, attribution: &HitAttribution) -> bool {
+        let DamageEvent { source_id, target_id, damage, hit_flag, hit_option, timestamp } = event;
+
+        // invincible targets take nothing and shared damage without a skill is already counted elsewhere
+        if hit_flag == HitFlag::INVINCIBLE
+            || (hit_flag == HitFlag::DAMAGE_SHARE && damage.skill_id == 0 && damage.skill_effect_id == 0)
+        {
+            return false;
+        }
+
+        let (Some(source_name), Some(target_name)) = (self.names.get(&source_id), self.names.get(&target_id))
+        else {
+            return false;
+        };
+        let (source_name, target_name) = (source_name.clone(), target_name.clone());
+
+        if self.encounter.fight_start == 0 || timestamp < self.encounter.fight_start {
+            self.encounter.fight_start = timestamp;
+            self.rebase(timestamp);
+        }
+        // a late event still counts, but leaves the latest state alone
+        let latest = timestamp >= self.encounter.last_combat_packet;
+        self.encounter.last_combat_packet = self.encounter.last_combat_packet.max(timestamp);
+        self.encounter.duration = self.encounter.last_combat_packet - self.encounter.fight_start;
+        let relative = timestamp - self.origin;
+
+        let amount = damage.damage;
+        let crit = hit_flag.is_crit();
+        let back_attack = hit_option.is_back_attack();
+        let front_attack = hit_option.is_front_attack();
+
+        let source = self.encounter.entities.get_mut(&source_name).expect("registered entity");
+        let source_is_player = source.entity_type == EntityType::PLAYER;
+
+        source.damage_stats.damage_dealt += amount;
+        source.skill_stats.hits += 1;
+        if crit {
+            source.damage_stats.crit_damage += amount;
+            source.skill_stats.crits += 1;
+        }
+        if back_attack {
+            source.damage_stats.back_attack_damage += amount;
+            source.skill_stats.back_attacks += 1;
+        }
+        if front_attack {
+            source.damage_stats.front_attack_damage += amount;
+            source.skill_stats.front_attacks += 1;
+        }
+
+        let skill = skill_entry(source, damage.skill_id);
+        let cast_damage = self.cast_damage.entry((source_id, damage.skill_id)).or_default();
+        // no cast of the skill started at or before the hit was seen, count the hit as one
+        let started = skill.skill_cast_log.partition_point(|cast| cast.timestamp <= relative);
+        let (index, implicit_cast) = match started {
+            0 => {
+                let index = start_cast(skill, relative, timestamp);
+                cast_damage.insert(index, 0);
+                (index, true)
+            }
+            after => (after - 1, false),
+        };
+        cast_damage[index] += amount;
+
+        skill.total_damage += amount;
+        skill.max_damage = skill.max_damage.max(amount);
+        skill.hits += 1;
+        if crit {
+            skill.crits += 1;
+            skill.crit_damage += amount;
+        }
+        if back_attack {
+            skill.back_attacks += 1;
+            skill.back_attack_damage += amount;
+        }
+        if front_attack {
+            skill.front_attacks += 1;
+            skill.front_attack_damage += amount;
+        }
+        skill.last_timestamp = skill.last_timestamp.max(timestamp);
+        attribution.apply_to_skill(skill, amount);
+
+        let mut hit = SkillHit {
+            timestamp: relative,
+            damage: amount,
+            crit,
+            back_attack,
+            front_attack,
+            ..Default::default()
+        };
+        attribution.apply_to_hit(&mut hit);
+        let cast = &mut skill.skill_cast_log[index];
+        cast.last = cast.last.max(relative);
+        let position = cast.hits.partition_point(|hit| hit.timestamp <= relative);
+        cast.hits.insert(position, hit);
+        skill.max_damage_cast = skill.max_damage_cast.max(cast_damage[index]);
+
+        if implicit_cast {
+            source.skill_stats.casts += 1;
+        }
+        attribution.apply_to_stats(&mut source.damage_stats, amount);
+        let source_damage = source.damage_stats.damage_dealt;
+
+        let target = self.encounter.entities.get_mut(&target_name).expect("registered entity");
+        let target_is_player = target.entity_type == EntityType::PLAYER;
+        target.damage_stats.damage_taken += amount;
+        if latest {
+            target.current_hp = damage.target_current_hp;
+            target.max_hp = damage.target_max_hp;
+            if target.entity_type == EntityType::BOSS {
+                self.encounter.current_boss_name = target_name;
+            }
+        }
+        let target_damage = target.damage_stats.damage_taken;
+
+        let stats = &mut self.encounter.encounter_damage_stats;
+        if source_is_player {
+            stats.total_damage_dealt += amount;
+            stats.top_damage_dealt = stats.top_damage_dealt.max(source_damage);
+        }
+        if target_is_player {
+            stats.total_damage_taken += amount;
+            stats.top_damage_taken = stats.top_damage_taken.max(target_damage);
+        }
+
+        true
+    }
+
+    /// Moves the origin of every relative time to `origin`.
+    fn rebase(&mut self, origin: i64) {
+        let shift = self.origin - origin;
+        let had_origin = self.origin != 0;
+        self.origin = origin;
+        if !had_origin || shift == 0 {
+            return;
+        }
+
+        for skill in self.encounter.entities.values_mut().flat_map(|entity| entity.skills.values_mut()) {
+            for cast in &mut skill.cast_log {
+                *cast += shift as i32;
+            }
+            for cast in &mut skill.skill_cast_log {
+                cast.timestamp += shift;
+                cast.last += shift;
+                for hit in &mut cast.hits {
+                    hit.timestamp += shift;
+                }
+            }
+        }
+    }
+
+    /// Fills in dps and the current boss and hands over the encounter.
+    pub fn finish(mut self) -> Encounter {
+        let encounter = &mut self.encounter;
+        let seconds = encounter.duration / 1000;
+
+        if seconds > 0 {
+            for entity in encounter.entities.values_mut() {
+                entity.damage_stats.dps = entity.damage_stats.damage_dealt / seconds;
+                for skill in entity.skills.values_mut() {
+                    skill.dps = skill.total_damage / seconds;
+                }
+            }
+            let stats = &mut encounter.encounter_damage_stats;
+            stats.dps = stats.total_damage_dealt / seconds;
+        }
+
+        encounter.current_boss = encounter.entities.get(&encounter.current_boss_name).cloned();
+        self.encounter
+    }
+}
+
+fn skill_entry(entity: &mut EncounterEntity, skill_id: u32) -> &mut Skill {
+    entity.skills.entry(skill_id).or_insert_with(|| Skill { id: skill_id, ..Default::default() })
+}
+
+/// Inserts the cast in time order and returns its index, `cast_log` stays parallel to `skill_cast_log`.
+fn start_cast(skill: &mut Skill, relative: i64, timestamp: i64) -> usize {
+    let index = skill.skill_cast_log.partition_point(|cast| cast.timestamp <= relative);
+    skill.casts += 1;
+    skill.cast_log.insert(index, relative as i32);
+    skill.skill_cast_log.insert(index, SkillCast { timestamp: relative, last: relative, hits: Vec::new() });
+    skill.last_timestamp = skill.last_timestamp.max(timestamp);
+    index
+}
@@ -0,0 +1,140 @@
+use std::sync::{Mutex, OnceLock, PoisonError};
+use hashbrown::HashMap;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use compact_str::CompactString;
+
+use crate::assets::{parse_json_str, AssetSource};
+use crate::error::AssetError;
+
+/// One named enum from `Enums.json`, e.g. `afkkickchecktype`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(from = "HashMap<u32, CompactString>")]
+pub struct GameEnum {
+    names: HashMap<u32, CompactString>,
+    ids: HashMap<CompactString, u32>,
+}
+
+impl From<HashMap<u32, CompactString>> for GameEnum {
+    fn from(names: HashMap<u32, CompactString>) -> Self {
+        let ids = names.iter().map(|(id, name)| (name.clone(), *id)).collect();
+        Self { names, ids }
+    }
+}
+
+impl GameEnum {
+    pub fn name_of(&self, id: u32) -> Option<&str> {
+        self.names.get(&id).map(CompactString::as_str)
+    }
+
+    pub fn id_of(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names.iter().map(|(id, name)| (*id, name.as_str()))
+    }
+}
+
+struct EnumEntry {
+    // lazy mode only, taken and dropped when the enum is first looked up
+    raw: Mutex<Option<Box<RawValue>>>,
+    table: OnceLock<Result<GameEnum, AssetError>>,
+}
+
+impl EnumEntry {
+    fn get(&self, enum_name: &str) -> Result<&GameEnum, &AssetError> {
+        self.table
+            .get_or_init(|| {
+                let raw = self.raw.lock().unwrap_or_else(PoisonError::into_inner).take();
+                let Some(raw) = raw else {
+                    return Ok(GameEnum::default());
+                };
+
+                // the path in the error starts at the enum, not at the top of the file
+                parse_json_str(&format!("Enums.json ({enum_name})"), raw.get())
+            })
+            .as_ref()
+    }
+}
+
+/// Id <-> name lookups over every enum in `Enums.json`.
+pub struct EnumRegistry {
+    enums: HashMap<CompactString, EnumEntry>,
+}
+
+impl EnumRegistry {
+    /// Parses every enum up front.
+    pub fn load<S: AssetSource>(source: &mut S) -> Result<Self, AssetError> {
+        let tables: HashMap<CompactString, GameEnum> = source.load("Enums.json")?;
+        let enums = tables
+            .into_iter()
+            .map(|(name, table)| {
+                let entry = EnumEntry {
+                    raw: Mutex::new(None),
+                    table: OnceLock::from(Ok(table)),
+                };
+                (name, entry)
+            })
+            .collect();
+
+        Ok(Self { enums })
+    }
+
+    /// Only splits the file into its enums, each one is parsed the first time it is looked up.
+    ///
+    /// `source` has to be backed by serde_json, `RawValue` is not supported by the simd sources.
+    pub fn load_lazy<S: AssetSource>(source: &mut S) -> Result<Self, AssetError> {
+        let raw_enums: HashMap<CompactString, Box<RawValue>> = source.load("Enums.json")?;
+        let enums = raw_enums
+            .into_iter()
+            .map(|(name, raw)| {
+                let entry = EnumEntry {
+                    raw: Mutex::new(Some(raw)),
+                    table: OnceLock::new(),
+                };
+                (name, entry)
+            })
+            .collect();
+
+        Ok(Self { enums })
+    }
+
+    /// `Ok(None)` for enums the file does not have. In lazy mode a malformed enum fails here,
+    /// on every lookup, rather than in [`load_lazy`](Self::load_lazy).
+    pub fn get(&self, enum_name: &str) -> Result<Option<&GameEnum>, &AssetError> {
+        self.enums.get(enum_name).map(|entry| entry.get(enum_name)).transpose()
+    }
+
+    pub fn name_of(&self, enum_name: &str, id: u32) -> Result<Option<&str>, &AssetError> {
+        Ok(self.get(enum_name)?.and_then(|table| table.name_of(id)))
+    }
+
+    pub fn id_of(&self, enum_name: &str, name: &str) -> Result<Option<u32>, &AssetError> {
+        Ok(self.get(enum_name)?.and_then(|table| table.id_of(name)))
+    }
+
+    pub fn contains(&self, enum_name: &str) -> bool {
+        self.enums.contains_key(enum_name)
+    }
+
+    pub fn enum_names(&self) -> impl Iterator<Item = &str> {
+        self.enums.keys().map(CompactString::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.enums.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.enums.is_empty()
+    }
+}
@@ -0,0 +1,130 @@
+use std::{fmt, io};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AssetError {
+    #[error("asset file {path} is missing")]
+    MissingFile { path: String },
+    #[error("could not read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("could not parse {file} at {location}, path `{path}`: {message}")]
+    Parse {
+        file: String,
+        location: ParseLocation,
+        path: String,
+        message: String,
+    },
+    #[error("could not encode asset cache {path}: {message}")]
+    Cache { path: String, message: String },
+    #[error("invalid asset archive {path}: {message}")]
+    Archive { path: String, message: String },
+}
+
+/// Where in the source a parse error happened, as far as the parser can tell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLocation {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl fmt::Display for ParseLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column, self.offset) {
+            (Some(line), Some(column), Some(offset)) => {
+                write!(f, "line {line} column {column} (byte {offset})")
+            }
+            (Some(line), Some(column), None) => write!(f, "line {line} column {column}"),
+            (_, _, Some(offset)) => write!(f, "byte {offset}"),
+            _ => write!(f, "unknown position"),
+        }
+    }
+}
+
+impl AssetError {
+    pub fn io(path: &str, source: io::Error) -> Self {
+        if source.kind() == io::ErrorKind::NotFound {
+            return Self::MissingFile { path: path.into() };
+        }
+
+        Self::Io { path: path.into(), source }
+    }
+
+    pub fn json(file: &str, err: serde_path_to_error::Error<serde_json::Error>, bytes: Option<&[u8]>) -> Self {
+        let path = err.path().to_string();
+        let err = err.into_inner();
+        let (line, column) = (err.line(), err.column());
+
+        if err.is_io() {
+            let source = io::Error::from(err);
+            return Self::io(file, source);
+        }
+
+        // serde_json appends the position to its message, `location` already carries it
+        let message = err.to_string();
+        let message = match message.strip_suffix(&format!(" at line {line} column {column}")) {
+            Some(message) if line > 0 => message.to_string(),
+            _ => message,
+        };
+
+        Self::Parse {
+            file: file.into(),
+            location: ParseLocation {
+                line: (line > 0).then_some(line),
+                column: (line > 0).then_some(column),
+                offset: bytes.and_then(|bytes| offset_of(bytes, line, column)),
+            },
+            path,
+            message,
+        }
+    }
+
+    pub fn simd(file: &str, path: String, err: simd_json::Error) -> Self {
+        Self::Parse {
+            file: file.into(),
+            location: ParseLocation {
+                // simd-json only tracks positions while building the tape, data errors report 0
+                offset: (!err.is_data()).then(|| err.index()),
+                ..Default::default()
+            },
+            path,
+            // the error's own `Display` adds a position, which is 0 for data errors
+            message: match err.error() {
+                simd_json::ErrorType::Serde(message) => message.clone(),
+                error => format!("{error:?}"),
+            },
+        }
+    }
+}
+
+// serde_json only reports 1-based line and column, so walk the buffer to turn them into a byte offset
+fn offset_of(bytes: &[u8], line: usize, column: usize) -> Option<usize> {
+    if line == 0 {
+        return None;
+    }
+
+    let line_start = if line == 1 {
+        0
+    } else {
+        bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte == b'\n')
+            .nth(line - 2)
+            .map(|(index, _)| index + 1)?
+    };
+
+    Some(line_start + column.saturating_sub(1))
+}
+
+/// A raw hit flag or hit option outside the game's enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum HitDecodeError {
+    #[error("{0} is not a hit flag")]
+    Flag(u32),
+    #[error("{0} is not a hit option")]
+    Option(i32),
+}
@@ -0,0 +1,108 @@
+use hashbrown::HashMap;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use compact_str::CompactString;
+
+/// `GemSkillGroup.json` as written, group id to `(name, icon, skill ids)`.
+pub type RawGemSkillGroups = HashMap<CompactString, (CompactString, CompactString, Vec<u32>)>;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GemSkillGroup {
+    pub id: u32,
+    // empty for the per-class "every skill" groups
+    pub name: CompactString,
+    pub icon: CompactString,
+    pub skills: Vec<u32>,
+}
+
+/// Gem skill groups with a reverse index from skill id to the groups that contain it.
+///
+/// Entries whose key is not a numeric group id are kept aside and logged
+/// instead of being dropped, see [`GemSkillGroupRegistry::malformed_keys`].
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "RawGemSkillGroups", into = "RawGemSkillGroups")]
+pub struct GemSkillGroupRegistry {
+    groups: HashMap<u32, GemSkillGroup>,
+    skill_index: HashMap<u32, Vec<u32>>,
+    malformed: RawGemSkillGroups,
+}
+
+impl From<RawGemSkillGroups> for GemSkillGroupRegistry {
+    fn from(raw: RawGemSkillGroups) -> Self {
+        let mut groups = HashMap::with_capacity(raw.len());
+        let mut malformed = RawGemSkillGroups::new();
+
+        for (key, (name, icon, skills)) in raw {
+            let Ok(id) = key.parse::<u32>() else {
+                warn!(
+                    "GemSkillGroup.json: group key {key:?} is not a numeric id, {} skills left unindexed",
+                    skills.len()
+                );
+                malformed.insert(key, (name, icon, skills));
+                continue;
+            };
+
+            groups.insert(id, GemSkillGroup { id, name, icon, skills });
+        }
+
+        let mut skill_index: HashMap<u32, Vec<u32>> = HashMap::new();
+        for group in groups.values() {
+            for skill_id in &group.skills {
+                skill_index.entry(*skill_id).or_default().push(group.id);
+            }
+        }
+        // hash order is not stable, keep lookups deterministic
+        skill_index.values_mut().for_each(|group_ids| group_ids.sort_unstable());
+
+        Self { groups, skill_index, malformed }
+    }
+}
+
+impl From<GemSkillGroupRegistry> for RawGemSkillGroups {
+    fn from(registry: GemSkillGroupRegistry) -> Self {
+        let mut raw = registry.malformed;
+        raw.extend(
+            registry
+                .groups
+                .into_values()
+                .map(|group| (group.id.to_string().into(), (group.name, group.icon, group.skills))),
+        );
+        raw
+    }
+}
+
+impl GemSkillGroupRegistry {
+    pub fn get(&self, group_id: u32) -> Option<&GemSkillGroup> {
+        self.groups.get(&group_id)
+    }
+
+    pub fn skills_of(&self, group_id: u32) -> Option<&[u32]> {
+        self.groups.get(&group_id).map(|group| group.skills.as_slice())
+    }
+
+    /// Every group `skill_id` belongs to, in group id order.
+    pub fn groups_of(&self, skill_id: u32) -> impl Iterator<Item = &GemSkillGroup> {
+        self.skill_index
+            .get(&skill_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|group_id| self.groups.get(group_id))
+    }
+
+    /// Keys of `GemSkillGroup.json` that could not be read as a group id.
+    pub fn malformed_keys(&self) -> impl Iterator<Item = &str> {
+        self.malformed.keys().map(CompactString::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GemSkillGroup> {
+        self.groups.values()
+    }
+}
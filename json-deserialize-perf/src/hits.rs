@@ -0,0 +1,83 @@
+use crate::error::HitDecodeError;
+use crate::models::*;
+
+impl HitFlag {
+    const ALL: [HitFlag; 14] = [
+        HitFlag::NORMAL,
+        HitFlag::CRITICAL,
+        HitFlag::MISS,
+        HitFlag::INVINCIBLE,
+        HitFlag::DOT,
+        HitFlag::IMMUNE,
+        HitFlag::IMMUNE_SILENCED,
+        HitFlag::FONT_SILENCED,
+        HitFlag::DOT_CRITICAL,
+        HitFlag::DODGE,
+        HitFlag::REFLECT,
+        HitFlag::DAMAGE_SHARE,
+        HitFlag::DODGE_HIT,
+        HitFlag::MAX,
+    ];
+
+    pub fn is_crit(self) -> bool {
+        matches!(self, HitFlag::CRITICAL | HitFlag::DOT_CRITICAL)
+    }
+
+    pub fn is_dot(self) -> bool {
+        matches!(self, HitFlag::DOT | HitFlag::DOT_CRITICAL)
+    }
+}
+
+impl TryFrom<u32> for HitFlag {
+    type Error = HitDecodeError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::ALL.get(value as usize).copied().ok_or(HitDecodeError::Flag(value))
+    }
+}
+
+impl HitOption {
+    const ALL: [HitOption; 5] = [
+        HitOption::NONE,
+        HitOption::BACK_ATTACK,
+        HitOption::FRONTAL_ATTACK,
+        HitOption::FLANK_ATTACK,
+        HitOption::MAX,
+    ];
+
+    pub fn is_back_attack(self) -> bool {
+        self == HitOption::BACK_ATTACK
+    }
+
+    pub fn is_front_attack(self) -> bool {
+        self == HitOption::FRONTAL_ATTACK
+    }
+}
+
+impl TryFrom<i32> for HitOption {
+    type Error = HitDecodeError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        usize::try_from(value)
+            .ok()
+            .and_then(|index| Self::ALL.get(index))
+            .copied()
+            .ok_or(HitDecodeError::Option(value))
+    }
+}
+
+impl DamageData {
+    /// Splits `modifier` into its hit flag (low 4 bits) and hit option (next 3 bits),
+    /// see [`decode_modifier`].
+    pub fn hit(&self) -> Result<(HitFlag, HitOption), HitDecodeError> {
+        decode_modifier(self.modifier)
+    }
+}
+
+/// The option field is one above [`HitOption`], 2 is a back attack. Both 0 and 1 decode to `NONE`,
+/// the same way the reference meters read it.
+pub fn decode_modifier(modifier: i32) -> Result<(HitFlag, HitOption), HitDecodeError> {
+    let flag = HitFlag::try_from((modifier & 0xf) as u32)?;
+    let option = HitOption::try_from((((modifier >> 4) & 0x7) - 1).max(0))?;
+    Ok((flag, option))
+}
@@ -0,0 +1,133 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use compact_str::CompactString;
+
+use crate::models::*;
+
+/// `ItemSet.json` as written, set name to set.
+pub type ItemSetMap = HashMap<CompactString, ItemSetData>;
+
+/// Item sets with a reverse index from item id to the set the item belongs to.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "ItemSetMap", into = "ItemSetMap")]
+pub struct ItemSetRegistry {
+    sets: ItemSetMap,
+    item_index: HashMap<u32, CompactString>,
+}
+
+impl From<ItemSetMap> for ItemSetRegistry {
+    fn from(sets: ItemSetMap) -> Self {
+        let item_index = sets
+            .iter()
+            .flat_map(|(name, set)| {
+                set.levels
+                    .values()
+                    .flat_map(|level| level.item_ids.iter())
+                    .map(move |item_id| (*item_id, name.clone()))
+            })
+            .collect();
+
+        Self { sets, item_index }
+    }
+}
+
+impl From<ItemSetRegistry> for ItemSetMap {
+    fn from(registry: ItemSetRegistry) -> Self {
+        registry.sets
+    }
+}
+
+/// A set bonus that is active for the equipped items.
+#[derive(Debug, Clone)]
+pub struct ActiveSetBonus<'a> {
+    pub set: &'a ItemSetData,
+    pub level: u8,
+    // pieces the bonus requires
+    pub count: u8,
+    pub bonus: &'a ItemSetCountData,
+}
+
+impl<'a> ActiveSetBonus<'a> {
+    /// The bonus' stat options without the `none` padding.
+    pub fn options(&self) -> impl Iterator<Item = &'a PassiveOption> {
+        self.bonus.options.iter().filter(|option| option.option_type != "none")
+    }
+}
+
+impl ItemSetRegistry {
+    pub fn get(&self, set_name: &str) -> Option<&ItemSetData> {
+        self.sets.get(set_name)
+    }
+
+    pub fn set_of(&self, item_id: u32) -> Option<&ItemSetData> {
+        self.item_index.get(&item_id).and_then(|name| self.sets.get(name))
+    }
+
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ItemSetData> {
+        self.sets.values()
+    }
+
+    /// Active bonuses for `equipped` pairs of item id and the item's set level.
+    ///
+    /// A bonus that needs `n` pieces is granted at the highest level that `n`
+    /// equipped pieces of the set have all reached. Items outside any set are ignored,
+    /// results are ordered by set name and then by pieces required.
+    pub fn active_bonuses(&self, equipped: &[(u32, u8)]) -> Vec<ActiveSetBonus<'_>> {
+        let mut pieces: HashMap<&str, Vec<(u32, u8)>> = HashMap::new();
+        for (item_id, level) in equipped {
+            if let Some(name) = self.item_index.get(item_id) {
+                pieces.entry(name.as_str()).or_default().push((*item_id, *level));
+            }
+        }
+
+        let mut bonuses = Vec::new();
+        for (name, mut items) in pieces {
+            let set = &self.sets[name];
+
+            // the same item equipped twice still only counts once
+            items.sort_unstable();
+            items.dedup_by_key(|(item_id, _)| *item_id);
+            let mut levels: Vec<u8> = items.into_iter().map(|(_, level)| level).collect();
+            levels.sort_unstable_by(|a, b| b.cmp(a));
+
+            // every level lists the same counts, take them from the lowest one
+            let Some((_, counts)) = set.levels.iter().min_by_key(|(level, _)| **level) else {
+                continue;
+            };
+
+            for count in counts.value.set_count_datas.keys().copied() {
+                let Some(reached) = levels.get(usize::from(count).saturating_sub(1)).copied() else {
+                    continue;
+                };
+
+                let best = set
+                    .levels
+                    .iter()
+                    .filter(|(level, data)| {
+                        **level <= reached && data.value.set_count_datas.contains_key(&count)
+                    })
+                    .max_by_key(|(level, _)| **level);
+
+                if let Some((level, data)) = best {
+                    bonuses.push(ActiveSetBonus {
+                        set,
+                        level: *level,
+                        count,
+                        bonus: &data.value.set_count_datas[&count],
+                    });
+                }
+            }
+        }
+
+        bonuses.sort_by(|a, b| a.set.name.cmp(&b.set.name).then(a.count.cmp(&b.count)));
+        bonuses
+    }
+}
@@ -1,7 +1,29 @@
 pub mod models;
+pub mod models_ref;
+pub mod assets;
+pub mod attribution;
+pub mod buff_flags;
+pub mod classes;
+pub mod combat_effects;
+pub mod encounter_builder;
+pub mod enums;
+pub mod gem_skills;
+pub mod hits;
+pub mod item_sets;
+pub mod raids;
+pub mod skill_index;
+pub mod stats;
+pub mod support_buffs;
+pub mod error;
+pub mod background;
+pub mod cache;
+pub mod archive;
 pub mod deser_reader_simd;
 pub mod deser_reader;
 pub mod deser_alloc_buff_in_one_go;
 pub mod deser_simd_alloc_buff_in_one_go;
 pub mod deser_include_str;
-pub mod deser_read_string;
\ No newline at end of file
+pub mod deser_read_string;
+pub mod deser_parallel;
+pub mod deser_mmap;
+pub mod deser_borrowed;
\ No newline at end of file
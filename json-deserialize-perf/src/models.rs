@@ -3,12 +3,11 @@ use std::str::FromStr;
 use std::fmt::Display;
 use bitflags::bitflags;
 use hashbrown::{HashMap, HashSet};
-use log::error;
+use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
-use serde_json::{Map, Value};
 use serde_with::serde_as;
-use serde_with::DefaultOnError;
 use compact_str::CompactString;
+use rkyv::{Archive, Deserialize as ArchiveDeserialize, Serialize as ArchiveSerialize};
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Copy, Clone)]
 #[allow(non_camel_case_types)]
@@ -59,8 +58,9 @@ impl FromStr for EntityType {
     }
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
-#[serde(rename_all = "camelCase")]
+/// Deserializing rebuilds the fields saved logs leave out, see [`Encounter::rebuild_skipped`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(remote = "Self", rename_all = "camelCase")]
 pub struct Encounter {
     pub last_combat_packet: i64,
     pub fight_start: i64,
@@ -80,7 +80,54 @@ pub struct Encounter {
     pub region: Option<CompactString>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+impl Serialize for Encounter {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Encounter::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Encounter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut encounter = Encounter::deserialize(deserializer)?;
+        encounter.rebuild_skipped();
+        Ok(encounter)
+    }
+}
+
+impl Encounter {
+    /// Fills in what `#[serde(skip)]` drops from a saved log:
+    /// `Skill::last_timestamp` from the latest cast (saved cast times are relative to `fight_start`)
+    /// and `unknown_buffs` from every buff id a skill or entity was buffed or debuffed by
+    /// that has no entry in the encounter's buff tables.
+    pub fn rebuild_skipped(&mut self) {
+        let stats = &mut self.encounter_damage_stats;
+        stats.unknown_buffs.clear();
+
+        for entity in self.entities.values_mut().chain(self.current_boss.as_mut()) {
+            for skill in entity.skills.values_mut() {
+                let last_cast = skill.skill_cast_log.iter().map(|cast| cast.last.max(cast.timestamp)).max();
+                let last_logged = skill.cast_log.iter().max().map(|offset| i64::from(*offset));
+                if let Some(offset) = last_cast.max(last_logged) {
+                    skill.last_timestamp = self.fight_start + offset;
+                }
+            }
+
+            let buff_ids = entity
+                .skills
+                .values()
+                .flat_map(|skill| skill.buffed_by.keys().chain(skill.debuffed_by.keys()))
+                .chain(entity.damage_stats.buffed_by.keys())
+                .chain(entity.damage_stats.debuffed_by.keys());
+            for buff_id in buff_ids {
+                if !stats.buffs.contains_key(buff_id) && !stats.debuffs.contains_key(buff_id) {
+                    stats.unknown_buffs.insert(*buff_id);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct EncounterDamageStats {
     pub total_damage_dealt: i64,
@@ -420,7 +467,7 @@ impl BossHpLog {
     }
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 pub struct Npc {
     pub id: i32,
     pub name: Option<CompactString>,
@@ -429,7 +476,7 @@ pub struct Npc {
     pub npc_type: CompactString,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Esther {
     pub name: CompactString,
     pub icon: CompactString,
@@ -438,7 +485,7 @@ pub struct Esther {
     pub npc_ids: Vec<u32>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillData {
     pub id: i32,
@@ -458,7 +505,7 @@ pub struct SkillData {
     pub is_hyper_awakening: bool,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillEffectData {
     pub id: i32,
@@ -474,7 +521,7 @@ pub struct SkillEffectData {
     pub values: Vec<i32>,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillBuffData {
     pub id: i32,
@@ -483,14 +530,13 @@ pub struct SkillBuffData {
     pub icon: Option<CompactString>,
     pub icon_show_type: Option<CompactString>,
     pub duration: i32,
-    // buff | debuff
-    pub category: CompactString,
+    pub category: StatusEffectCategory,
     #[serde(rename(deserialize = "type"))]
-    #[serde(deserialize_with = "int_or_string_as_string")]
-    pub buff_type: CompactString,
+    #[serde(deserialize_with = "int_or_string_as")]
+    pub buff_type: SkillBuffType,
     pub status_effect_values: Option<Vec<i32>>,
-    pub buff_category: Option<CompactString>,
-    pub target: CompactString,
+    pub buff_category: Option<StatusEffectBuffCategory>,
+    pub target: SkillBuffTarget,
     pub unique_group: u32,
     #[serde(rename(deserialize = "overlap"))]
     pub overlap_flag: i32,
@@ -499,14 +545,14 @@ pub struct SkillBuffData {
     pub set_name: Option<CompactString>,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PerLevelData {
     pub passive_options: Vec<PassiveOption>,
     // pub status_effect_values: Vec<i32>
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PassiveOption {
     #[serde(rename(deserialize = "type"))]
@@ -520,13 +566,140 @@ pub struct PassiveOption {
 #[serde(rename_all = "camelCase")]
 pub struct StatusEffect {
     pub target: StatusEffectTarget,
-    pub category: CompactString,
-    pub buff_category: CompactString,
+    pub category: StatusEffectCategory,
+    pub buff_category: StatusEffectBuffCategory,
     pub buff_type: u32,
     pub unique_group: u32,
     pub source: StatusEffectSource,
 }
 
+impl StatusEffect {
+    pub fn buff_type_flags(&self) -> StatusEffectBuffTypeFlags {
+        StatusEffectBuffTypeFlags::from_bits_truncate(self.buff_type)
+    }
+}
+
+/// Declares an enum over the known values of a string field in the game data.
+/// Anything else is kept verbatim in `Unknown` so new patches still load,
+/// and values (de)serialize as the original string.
+macro_rules! string_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Archive, ArchiveSerialize, ArchiveDeserialize)]
+        pub enum $name {
+            $($variant,)*
+            Unknown(CompactString),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::Unknown(CompactString::default())
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(value.into()),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                CompactString::deserialize(deserializer).map(|value| Self::from(value.as_str()))
+            }
+        }
+    };
+}
+
+string_enum! {
+    StatusEffectCategory {
+        Buff => "buff",
+        Debuff => "debuff",
+    }
+}
+
+string_enum! {
+    StatusEffectBuffCategory {
+        ClassSkill => "classskill",
+        Identity => "identity",
+        Ability => "ability",
+        ArkPassive => "arkpassive",
+        Set => "set",
+        Bracelet => "bracelet",
+        Elixir => "elixir",
+        BattleItem => "battleitem",
+        DropsOfEther => "dropsofether",
+        Cook => "cook",
+        Pet => "pet",
+        Etc => "etc",
+    }
+}
+
+string_enum! {
+    /// Who a buff applies to in `SkillBuff.json`, see [`StatusEffectTarget`] for the meter side.
+    SkillBuffTarget {
+        None => "none",
+        SelfOnly => "self",
+        Party => "party",
+    }
+}
+
+impl From<&SkillBuffTarget> for StatusEffectTarget {
+    fn from(target: &SkillBuffTarget) -> Self {
+        match target {
+            SkillBuffTarget::SelfOnly => StatusEffectTarget::SELF,
+            SkillBuffTarget::Party => StatusEffectTarget::PARTY,
+            _ => StatusEffectTarget::OTHER,
+        }
+    }
+}
+
+string_enum! {
+    SkillBuffType {
+        Stat => "stat",
+        Shield => "shield",
+        WeakenDefense => "weaken_defense",
+        WeakenResistance => "weaken_resistance",
+        SkillDamageAmplify => "skill_damage_amplify",
+        BeattackedDamageAmplify => "beattacked_damage_amplify",
+        SkillDamageAmplifyAttack => "skill_damage_amplify_attack",
+        DirectionalAttackAmplify => "directional_attack_amplify",
+        InstantStatAmplify => "instant_stat_amplify",
+        InstantStatAmplifyByContents => "instant_stat_amplify_by_contents",
+        AttackPowerAmplify => "attack_power_amplify",
+        MoveSpeedDown => "move_speed_down",
+        AllSpeedDown => "all_speed_down",
+        ResetCooldown => "reset_cooldown",
+        ChangeAiPoint => "change_ai_point",
+        AiPointAmplify => "ai_point_amplify",
+        IncreaseIdentityGauge => "increase_identity_gauge",
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub enum StatusEffectTarget {
     #[default]
@@ -564,12 +737,12 @@ bitflags! {
     }
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 pub struct CombatEffectData {
     pub effects: Vec<CombatEffectDetail>,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 pub struct CombatEffectDetail {
     pub ratio: i32,
     pub cooldown: i32,
@@ -577,23 +750,64 @@ pub struct CombatEffectDetail {
     pub actions: Vec<CombatEffectAction>,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+/// See [`CombatEffectCondition::condition`] for the decoded condition with its argument.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase", default)]
 pub struct CombatEffectCondition {
     #[serde(rename(deserialize = "type"))]
-    pub condition_type: CompactString,
-    pub actor_type: CompactString,
+    pub condition_type: CombatEffectConditionType,
+    pub actor_type: CombatEffectActor,
     pub arg: i32,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+/// See [`CombatEffectAction::action`] for the decoded action with its arguments.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
 #[serde(rename_all = "camelCase", default)]
 pub struct CombatEffectAction {
-    pub action_type: CompactString,
-    pub actor_type: CompactString,
+    pub action_type: CombatEffectActionType,
+    pub actor_type: CombatEffectActor,
     pub args: Vec<i32>,
 }
 
+string_enum! {
+    CombatEffectActor {
+        SelfActor => "self",
+        Target => "target",
+        Caster => "caster",
+    }
+}
+
+string_enum! {
+    CombatEffectConditionType {
+        TargetCount => "target_count",
+        CurrentSkill => "current_skill",
+        PcSkill => "pc_skill",
+        SkillIdentityCategory => "skill_identity_category",
+        AbnormalMoveImmune => "abnormal_move_immune",
+        AbnormalMoveAll => "abnormal_move_all",
+        AbnormalMove => "abnormal_move",
+        AbnormalStatus => "abnormal_status",
+        HpLess => "hp_less",
+        NpcGradeLess => "npc_grade_less",
+        NpcGradeGreater => "npc_grade_greater",
+        IdentityStance => "identity_stance",
+        DirectionalAttack => "directional_attack",
+        Critical => "critical",
+    }
+}
+
+string_enum! {
+    CombatEffectActionType {
+        ModifyDamage => "modify_damage",
+        ModifyFinalDamage => "modify_final_damage",
+        ModifyCriticalRatio => "modify_critical_ratio",
+        ModifyCriticalMultiplier => "modify_critical_multiplier",
+        ModifyPenetration => "modify_penetration",
+        ModifyPenetrationWhenCritical => "modify_penetration_when_critical",
+        ModifyDamageShieldMultiplier => "modify_damage_shield_multiplier",
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase", default)]
 pub struct SkillFeatureOption {
@@ -605,11 +819,80 @@ pub struct SkillFeatureOption {
     pub param: Vec<i32>,
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+/// An `Ability.json` entry. The `slim-engravings` feature drops everything but
+/// `id`, `name` and `icon` for callers that only need to label engravings.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EngravingData {
     pub id: u32,
     pub name: Option<CompactString>,
     pub icon: Option<CompactString>,
+    #[cfg(not(feature = "slim-engravings"))]
+    pub levels: HashMap<u8, EngravingLevel>,
+    #[cfg(not(feature = "slim-engravings"))]
+    pub feature_type: CompactString,
+    #[cfg(not(feature = "slim-engravings"))]
+    pub is_engrave: bool,
+    #[cfg(not(feature = "slim-engravings"))]
+    pub is_penalty: bool,
+}
+
+#[cfg(not(feature = "slim-engravings"))]
+impl EngravingData {
+    pub fn level(&self, level: u8) -> Option<&EngravingLevel> {
+        self.levels.get(&level)
+    }
+
+    pub fn max_level(&self) -> Option<u8> {
+        self.levels.keys().max().copied()
+    }
+}
+
+#[cfg(not(feature = "slim-engravings"))]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
+pub struct EngravingLevel {
+    pub desc: Option<CompactString>,
+    pub level: u8,
+    pub values: Option<Vec<i32>>,
+    pub addons: Option<Vec<EngravingAddon>>,
+}
+
+#[cfg(not(feature = "slim-engravings"))]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Archive, ArchiveSerialize, ArchiveDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngravingAddon {
+    #[serde(rename(deserialize = "type"))]
+    pub addon_type: CompactString,
+    pub stat_type: CompactString,
+    pub key_index: i32,
+    pub key_value: i32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ItemSetData {
+    pub id: u32,
+    pub name: CompactString,
+    pub levels: HashMap<u8, ItemSetLevelData>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSetLevelData {
+    pub item_ids: Vec<u32>,
+    pub value: ItemSetValue,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSetValue {
+    // pieces required -> bonus
+    pub set_count_datas: HashMap<u8, ItemSetCountData>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ItemSetCountData {
+    pub desc: CompactString,
+    pub options: Vec<PassiveOption>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -663,7 +946,7 @@ pub struct EncounterDbInfo {
     pub total_encounters_filtered: i32,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 #[repr(i32)]
 pub enum HitOption {
@@ -674,7 +957,7 @@ pub enum HitOption {
     MAX,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 #[repr(u32)]
 pub enum HitFlag {
@@ -708,18 +991,59 @@ pub struct LocalPlayer {
     pub count: i32,
 }
 
-fn int_or_string_as_string<'de, D>(deserializer: D) -> Result<CompactString, D::Error>
+pub(crate) fn int_or_string_as<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
+    T: for<'a> From<&'a str>,
 {
-    let value = Value::deserialize(deserializer)?;
-    match value {
-        Value::String(s) => Ok(CompactString::from(s)),
-        Value::Number(n) => {
-            let mut s = CompactString::default();
-            write!(&mut s, "{}", n).unwrap();
-            Ok(s)
-        },
-        _ => Err(serde::de::Error::custom("Expected a string or an integer")),
+    int_or_string_as_string(deserializer).map(|value| T::from(value.as_str()))
+}
+
+pub(crate) fn int_or_string_as_string<'de, D>(deserializer: D) -> Result<CompactString, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // binary formats such as the asset cache store the already normalized string
+    if !deserializer.is_human_readable() {
+        return CompactString::deserialize(deserializer);
     }
+
+    deserializer.deserialize_any(IntOrStringVisitor)
 }
+
+struct IntOrStringVisitor;
+
+impl<'de> Visitor<'de> for IntOrStringVisitor {
+    type Value = CompactString;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string or a number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CompactString::from(v))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CompactString::from(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        let mut s = CompactString::default();
+        write!(&mut s, "{}", v).unwrap();
+        Ok(s)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        let mut s = CompactString::default();
+        write!(&mut s, "{}", v).unwrap();
+        Ok(s)
+    }
+
+    // `{:?}` keeps the trailing `.0` the way serde_json prints whole floats
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        let mut s = CompactString::default();
+        write!(&mut s, "{:?}", v).unwrap();
+        Ok(s)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+//! Borrowed counterparts of the asset models in [`crate::models`].
+//!
+//! Strings point into the JSON buffer they were parsed from and are only
+//! copied when the source contains escape sequences. Serde only borrows a
+//! bare `Cow`, optional strings go through `serde_with`'s `BorrowCow`.
+
+use std::borrow::Cow;
+use hashbrown::HashMap;
+use serde::Deserialize;
+use serde_with::{serde_as, BorrowCow};
+use compact_str::CompactString;
+
+use crate::models::{
+    int_or_string_as, int_or_string_as_string, SkillBuffTarget, SkillBuffType, StatusEffectBuffCategory,
+    StatusEffectCategory,
+};
+
+#[serde_as]
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct NpcRef<'a> {
+    pub id: i32,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub name: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub grade: Cow<'a, str>,
+    #[serde(rename = "type", borrow)]
+    pub npc_type: Cow<'a, str>,
+}
+
+#[serde_as]
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDataRef<'a> {
+    pub id: i32,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub name: Option<Cow<'a, str>>,
+    #[serde(rename = "type", default)]
+    #[serde(deserialize_with = "int_or_string_as_string")]
+    pub skill_type: CompactString,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub desc: Option<Cow<'a, str>>,
+    pub class_id: u32,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub icon: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub identity_category: Option<Cow<'a, str>>,
+    pub groups: Option<Vec<i32>>,
+    pub summon_source_skills: Option<Vec<u32>>,
+    pub source_skills: Option<Vec<u32>>,
+    #[serde(default)]
+    pub is_hyper_awakening: bool,
+}
+
+#[serde_as]
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillEffectDataRef<'a> {
+    pub id: i32,
+    #[serde(borrow)]
+    pub comment: Cow<'a, str>,
+    #[serde(skip)]
+    pub stagger: i32,
+    pub source_skills: Option<Vec<u32>>,
+    pub directional_mask: Option<i32>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub item_name: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub item_desc: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub item_type: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub icon: Option<Cow<'a, str>>,
+    pub values: Vec<i32>,
+}
+
+#[serde_as]
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillBuffDataRef<'a> {
+    pub id: i32,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub name: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub desc: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub icon: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub icon_show_type: Option<Cow<'a, str>>,
+    pub duration: i32,
+    pub category: StatusEffectCategory,
+    #[serde(rename(deserialize = "type"))]
+    #[serde(deserialize_with = "int_or_string_as")]
+    pub buff_type: SkillBuffType,
+    pub status_effect_values: Option<Vec<i32>>,
+    pub buff_category: Option<StatusEffectBuffCategory>,
+    pub target: SkillBuffTarget,
+    pub unique_group: u32,
+    #[serde(rename(deserialize = "overlap"))]
+    pub overlap_flag: i32,
+    #[serde(borrow)]
+    pub per_level_data: HashMap<CompactString, PerLevelDataRef<'a>>,
+    pub source_skills: Option<Vec<u32>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub set_name: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerLevelDataRef<'a> {
+    #[serde(borrow)]
+    pub passive_options: Vec<PassiveOptionRef<'a>>,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PassiveOptionRef<'a> {
+    #[serde(rename(deserialize = "type"), borrow)]
+    pub option_type: Cow<'a, str>,
+    #[serde(borrow)]
+    pub key_stat: Cow<'a, str>,
+    pub key_index: i32,
+    pub value: i32,
+}
+
+/// Mirrors [`EngravingData`](crate::models::EngravingData), including its `slim-engravings` gating.
+#[serde_as]
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EngravingDataRef<'a> {
+    pub id: u32,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub name: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub icon: Option<Cow<'a, str>>,
+    #[cfg(not(feature = "slim-engravings"))]
+    #[serde(borrow)]
+    pub levels: HashMap<u8, EngravingLevelRef<'a>>,
+    #[cfg(not(feature = "slim-engravings"))]
+    #[serde(borrow)]
+    pub feature_type: Cow<'a, str>,
+    #[cfg(not(feature = "slim-engravings"))]
+    pub is_engrave: bool,
+    #[cfg(not(feature = "slim-engravings"))]
+    pub is_penalty: bool,
+}
+
+#[cfg(not(feature = "slim-engravings"))]
+#[serde_as]
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct EngravingLevelRef<'a> {
+    #[serde_as(as = "Option<BorrowCow>")]
+    pub desc: Option<Cow<'a, str>>,
+    pub level: u8,
+    pub values: Option<Vec<i32>>,
+    #[serde(borrow)]
+    pub addons: Option<Vec<EngravingAddonRef<'a>>>,
+}
+
+#[cfg(not(feature = "slim-engravings"))]
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EngravingAddonRef<'a> {
+    #[serde(rename(deserialize = "type"), borrow)]
+    pub addon_type: Cow<'a, str>,
+    #[serde(borrow)]
+    pub stat_type: Cow<'a, str>,
+    pub key_index: i32,
+    pub key_value: i32,
+}
@@ -0,0 +1,196 @@
+use std::{fmt, marker::PhantomData};
+use hashbrown::{HashMap, HashSet};
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use compact_str::CompactString;
+
+/// A map that keeps its entries in file order, hash maps would lose the raid, gate and boss order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OrderedEntries<V>(pub Vec<(CompactString, V)>);
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for OrderedEntries<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for EntriesVisitor<V> {
+            type Value = OrderedEntries<V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor(PhantomData))
+    }
+}
+
+impl<V: Serialize> Serialize for OrderedEntries<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// `encounters.json` as written, raid to gate to bosses.
+pub type RawEncounters = OrderedEntries<OrderedEntries<Vec<CompactString>>>;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Raid {
+    pub name: CompactString,
+    pub gates: Vec<Gate>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Gate {
+    pub name: CompactString,
+    // in encounter order
+    pub bosses: Vec<CompactString>,
+}
+
+/// A boss showing up in more than one gate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateBoss<'a> {
+    pub boss: &'a str,
+    /// `(raid, gate)` pairs in file order.
+    pub locations: Vec<(&'a str, &'a str)>,
+}
+
+/// Raids, their gates and the bosses of each gate in `encounters.json` order,
+/// with a boss name index.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "RawEncounters", into = "RawEncounters")]
+pub struct RaidCatalog {
+    raids: Vec<Raid>,
+    // boss -> (raid index, gate index) for every gate the boss appears in, once per gate
+    boss_index: HashMap<CompactString, Vec<(usize, usize)>>,
+}
+
+impl From<RawEncounters> for RaidCatalog {
+    fn from(raw: RawEncounters) -> Self {
+        let raids: Vec<Raid> = raw
+            .0
+            .into_iter()
+            .map(|(name, gates)| Raid {
+                name,
+                gates: gates.0.into_iter().map(|(name, bosses)| Gate { name, bosses }).collect(),
+            })
+            .collect();
+
+        let mut boss_index: HashMap<CompactString, Vec<(usize, usize)>> = HashMap::new();
+        for (raid_index, raid) in raids.iter().enumerate() {
+            for (gate_index, gate) in raid.gates.iter().enumerate() {
+                for boss in &gate.bosses {
+                    let locations = boss_index.entry(boss.clone()).or_default();
+                    // a boss listed twice in one gate is still in one gate
+                    if locations.last() != Some(&(raid_index, gate_index)) {
+                        locations.push((raid_index, gate_index));
+                    }
+                }
+            }
+        }
+
+        Self { raids, boss_index }
+    }
+}
+
+impl From<RaidCatalog> for RawEncounters {
+    fn from(catalog: RaidCatalog) -> Self {
+        OrderedEntries(
+            catalog
+                .raids
+                .into_iter()
+                .map(|raid| {
+                    let gates = raid.gates.into_iter().map(|gate| (gate.name, gate.bosses)).collect();
+                    (raid.name, OrderedEntries(gates))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl RaidCatalog {
+    pub fn raids(&self) -> &[Raid] {
+        &self.raids
+    }
+
+    pub fn raid(&self, name: &str) -> Option<&Raid> {
+        self.raids.iter().find(|raid| raid.name == name)
+    }
+
+    pub fn contains_boss(&self, boss: &str) -> bool {
+        self.boss_index.contains_key(boss)
+    }
+
+    /// The first `(raid, gate)` a boss appears in, bosses can show up in several gates.
+    pub fn locate(&self, boss: &str) -> Option<(&str, &str)> {
+        self.locations_of(boss).next()
+    }
+
+    /// Every `(raid, gate)` a boss appears in, in file order.
+    pub fn locations_of(&self, boss: &str) -> impl Iterator<Item = (&str, &str)> {
+        self.boss_index
+            .get(boss)
+            .into_iter()
+            .flatten()
+            .map(|(raid_index, gate_index)| {
+                let raid = &self.raids[*raid_index];
+                (raid.name.as_str(), raid.gates[*gate_index].name.as_str())
+            })
+    }
+
+    /// Bosses of a raid in gate order, each listed once, ready for `SearchFilter::bosses`.
+    pub fn bosses_of(&self, raid: &str) -> Vec<CompactString> {
+        unique_bosses(self.raid(raid).into_iter().flat_map(|raid| &raid.gates))
+    }
+
+    /// Every raid boss, each listed once, for `SearchFilter::raids_only`.
+    pub fn all_bosses(&self) -> Vec<CompactString> {
+        unique_bosses(self.raids.iter().flat_map(|raid| &raid.gates))
+    }
+
+    /// Bosses that appear in more than one gate, in the order they are first met.
+    /// Bosses first met in the same gate keep their order within it.
+    pub fn duplicate_bosses(&self) -> Vec<DuplicateBoss<'_>> {
+        let mut duplicates: Vec<DuplicateBoss<'_>> = self
+            .boss_index
+            .iter()
+            .filter(|(_, locations)| locations.len() > 1)
+            .map(|(boss, _)| DuplicateBoss {
+                boss: boss.as_str(),
+                locations: self.locations_of(boss).collect(),
+            })
+            .collect();
+
+        duplicates.sort_by_cached_key(|duplicate| self.first_met(duplicate.boss));
+        duplicates
+    }
+
+    // (raid index, gate index, position in the gate) of the first mention of `boss`
+    fn first_met(&self, boss: &str) -> (usize, usize, usize) {
+        let (raid_index, gate_index) = self.boss_index[boss][0];
+        let gate = &self.raids[raid_index].gates[gate_index];
+        let position = gate.bosses.iter().position(|name| name == boss).unwrap_or_default();
+        (raid_index, gate_index, position)
+    }
+}
+
+fn unique_bosses<'a>(gates: impl Iterator<Item = &'a Gate>) -> Vec<CompactString> {
+    let mut seen = HashSet::new();
+    gates
+        .flat_map(|gate| &gate.bosses)
+        .filter(|boss| seen.insert(*boss))
+        .cloned()
+        .collect()
+}
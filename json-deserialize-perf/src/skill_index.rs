@@ -0,0 +1,85 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::models::*;
+
+/// Reverse lookups over the skill tables, built once while loading.
+/// Every list is sorted by id.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SkillIndex {
+    buffs_by_skill: HashMap<u32, Vec<u32>>,
+    effects_by_skill: HashMap<u32, Vec<u32>>,
+    skills_by_class: HashMap<u32, Vec<u32>>,
+    skills_by_group: HashMap<i32, Vec<u32>>,
+    summon_parents: HashMap<u32, u32>,
+}
+
+impl SkillIndex {
+    pub fn build(
+        skill_data: &HashMap<u32, SkillData>,
+        skill_buff_data: &HashMap<u32, SkillBuffData>,
+        skill_effect_data: &HashMap<u32, SkillEffectData>,
+    ) -> Self {
+        let mut index = Self::default();
+
+        for (buff_id, buff) in skill_buff_data {
+            for skill_id in buff.source_skills.iter().flatten() {
+                index.buffs_by_skill.entry(*skill_id).or_default().push(*buff_id);
+            }
+        }
+
+        for (effect_id, effect) in skill_effect_data {
+            for skill_id in effect.source_skills.iter().flatten() {
+                index.effects_by_skill.entry(*skill_id).or_default().push(*effect_id);
+            }
+        }
+
+        for (skill_id, skill) in skill_data {
+            index.skills_by_class.entry(skill.class_id).or_default().push(*skill_id);
+
+            for group_id in skill.groups.iter().flatten() {
+                index.skills_by_group.entry(*group_id).or_default().push(*skill_id);
+            }
+
+            // a summon lists the skill that spawned it first
+            if let Some(parent) = skill.summon_source_skills.as_ref().and_then(|skills| skills.first()) {
+                index.summon_parents.insert(*skill_id, *parent);
+            }
+        }
+
+        for ids in index
+            .buffs_by_skill
+            .values_mut()
+            .chain(index.effects_by_skill.values_mut())
+            .chain(index.skills_by_class.values_mut())
+            .chain(index.skills_by_group.values_mut())
+        {
+            ids.sort_unstable();
+        }
+
+        index
+    }
+
+    /// Buffs whose `source_skills` include `skill_id`.
+    pub fn buffs_of(&self, skill_id: u32) -> &[u32] {
+        self.buffs_by_skill.get(&skill_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Skill effects whose `source_skills` include `skill_id`.
+    pub fn effects_of(&self, skill_id: u32) -> &[u32] {
+        self.effects_by_skill.get(&skill_id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn skills_of_class(&self, class_id: u32) -> &[u32] {
+        self.skills_by_class.get(&class_id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn skills_of_group(&self, group_id: i32) -> &[u32] {
+        self.skills_by_group.get(&group_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The player skill that summoned `summon_skill_id`, `None` for skills that are not summons.
+    pub fn summon_parent(&self, summon_skill_id: u32) -> Option<u32> {
+        self.summon_parents.get(&summon_skill_id).copied()
+    }
+}
@@ -0,0 +1,192 @@
+use std::ops::RangeInclusive;
+use compact_str::ToCompactString;
+use hashbrown::HashMap;
+
+use crate::models::*;
+
+/// What a stat option changes, coarse enough to classify buffs by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatKind {
+    CritRate,
+    CritDamage,
+    /// Flat main stat or attack power.
+    AttackPower,
+    AttackPowerRate,
+    /// Skill, elemental, awakening or species damage %.
+    DamageRate,
+    Penetration,
+    /// Defense, resistance and damage taken.
+    Defense,
+    AttackSpeed,
+    /// Swiftness, attack speed and cooldown at once.
+    Swiftness,
+    MoveSpeed,
+    Cooldown,
+    Hp,
+    Resource,
+    Stagger,
+    Other,
+}
+
+/// One passive option read as a change to a stat, `value` is as written in the data
+/// (1/10000 for rates, flat otherwise).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatDelta<'a> {
+    pub kind: StatKind,
+    /// `key_stat` for `stat` options, the option type for skill options.
+    pub stat: &'a str,
+    /// Id from `StatType.json` for `stat` options.
+    pub stat_id: Option<u32>,
+    pub key_index: i32,
+    pub value: i32,
+}
+
+const CRIT_RATE: [&str; 3] = ["critical_hit_rate", "criticalhit", "criticalhit_x"];
+const ATTACK_POWER: [&str; 9] =
+    ["str", "agi", "int", "str_x", "agi_x", "int_x", "char_attack_dam", "attack_power_addend", "weapon_dam"];
+const ATTACK_POWER_RATE: [&str; 2] = ["attack_power_rate", "attack_power_rate_x"];
+const DAMAGE_RATE: [&str; 3] = ["skill_damage_rate", "skill_damage_rate_x", "awakening_dam_rate"];
+const PENETRATION: [&str; 2] = ["def_pen_rate", "res_pen_rate"];
+const SWIFTNESS: [&str; 2] = ["rapidity", "rapidity_x"];
+const ATTACK_SPEED: [&str; 2] = ["attack_speed", "attack_speed_rate"];
+const STAGGER: [&str; 3] = ["mastery", "mastery_x", "paralyzation_point_rate"];
+const RESOURCE: [&str; 8] = [
+    "max_mp",
+    "max_mp_x",
+    "max_mp_x_x",
+    "normal_mp_recovery",
+    "combat_mp_recovery",
+    "normal_mp_recovery_rate",
+    "combat_mp_recovery_rate",
+    "resource_recovery_rate",
+];
+const HP: [&str; 12] = [
+    "con",
+    "con_x",
+    "max_hp",
+    "max_hp_x",
+    "max_hp_x_x",
+    "normal_hp_recovery",
+    "combat_hp_recovery",
+    "normal_hp_recovery_rate",
+    "combat_hp_recovery_rate",
+    "self_recovery_rate",
+    "drain_hp_dam_rate",
+    "vitality",
+];
+
+/// Turns [`PassiveOption`]s into [`StatDelta`]s, using `StatType.json` ids
+/// to place stats that come in contiguous families (defense, move speed, elemental damage, ...).
+#[derive(Debug, Clone)]
+pub struct StatResolver<'a> {
+    stat_type_map: &'a HashMap<String, u32>,
+    defense: Option<RangeInclusive<u32>>,
+    move_speed: Option<RangeInclusive<u32>>,
+    elemental_damage: Option<RangeInclusive<u32>>,
+    species_damage: Option<RangeInclusive<u32>>,
+    sub_rates: Option<RangeInclusive<u32>>,
+}
+
+impl<'a> StatResolver<'a> {
+    pub fn new(stat_type_map: &'a HashMap<String, u32>) -> Self {
+        let range = |first: &str, last: &str| Some(*stat_type_map.get(first)?..=*stat_type_map.get(last)?);
+
+        Self {
+            stat_type_map,
+            defense: range("def", "magical_inc_rate"),
+            move_speed: range("move_speed", "vehicle_move_speed_rate"),
+            elemental_damage: range("fire_dam_rate", "elements_dam_rate"),
+            species_damage: range("npc_species_humanoid_dam_rate", "npc_species_archfiend_dam_rate"),
+            sub_rates: range("attack_power_sub_rate_1", "skill_damage_sub_rate_2"),
+        }
+    }
+
+    pub fn stat_id(&self, key_stat: &str) -> Option<u32> {
+        self.stat_type_map.get(key_stat).copied()
+    }
+
+    /// The kind of a `StatType.json` stat, `Other` for names the map does not know.
+    pub fn stat_kind(&self, key_stat: &str) -> StatKind {
+        let Some(id) = self.stat_id(key_stat) else {
+            return StatKind::Other;
+        };
+        let within = |range: &Option<RangeInclusive<u32>>| range.as_ref().is_some_and(|range| range.contains(&id));
+
+        if CRIT_RATE.contains(&key_stat) {
+            StatKind::CritRate
+        } else if key_stat == "critical_dam_rate" {
+            StatKind::CritDamage
+        } else if ATTACK_POWER.contains(&key_stat) {
+            StatKind::AttackPower
+        } else if ATTACK_POWER_RATE.contains(&key_stat) {
+            StatKind::AttackPowerRate
+        } else if DAMAGE_RATE.contains(&key_stat)
+            || within(&self.elemental_damage)
+            || within(&self.species_damage)
+            || within(&self.sub_rates)
+        {
+            StatKind::DamageRate
+        } else if PENETRATION.contains(&key_stat) {
+            StatKind::Penetration
+        } else if within(&self.defense) || key_stat == "endurance" || key_stat == "endurance_x" {
+            StatKind::Defense
+        } else if SWIFTNESS.contains(&key_stat) {
+            StatKind::Swiftness
+        } else if ATTACK_SPEED.contains(&key_stat) {
+            StatKind::AttackSpeed
+        } else if within(&self.move_speed) {
+            StatKind::MoveSpeed
+        } else if key_stat == "cooldown_reduction" {
+            StatKind::Cooldown
+        } else if HP.contains(&key_stat) {
+            StatKind::Hp
+        } else if RESOURCE.contains(&key_stat) {
+            StatKind::Resource
+        } else if STAGGER.contains(&key_stat) {
+            StatKind::Stagger
+        } else {
+            StatKind::Other
+        }
+    }
+
+    /// `None` for the `none` padding and for stats `StatType.json` does not list.
+    pub fn option_delta(&self, option: &'a PassiveOption) -> Option<StatDelta<'a>> {
+        let (kind, stat, stat_id) = match option.option_type.as_str() {
+            "none" => return None,
+            "stat" => {
+                let stat_id = self.stat_id(&option.key_stat)?;
+                (self.stat_kind(&option.key_stat), option.key_stat.as_str(), Some(stat_id))
+            }
+            option_type => {
+                let kind = match option_type {
+                    "skill_critical_ratio" => StatKind::CritRate,
+                    "skill_critical_damage" => StatKind::CritDamage,
+                    "skill_damage" | "class_option" | "skill_group_damage" => StatKind::DamageRate,
+                    "skill_penetration" => StatKind::Penetration,
+                    "skill_cooldown_reduction" | "skill_group_cooldown_reduction" => StatKind::Cooldown,
+                    "skill_mana_reduction" | "mana_reduction" => StatKind::Resource,
+                    _ => StatKind::Other,
+                };
+                (kind, option_type, None)
+            }
+        };
+
+        Some(StatDelta {
+            kind,
+            stat,
+            stat_id,
+            key_index: option.key_index,
+            value: option.value,
+        })
+    }
+
+    /// Stat deltas of `buff` at `level`, empty when the buff has no data for that level.
+    pub fn resolve(&self, buff: &'a SkillBuffData, level: u32) -> Vec<StatDelta<'a>> {
+        buff.per_level_data
+            .get(level.to_compact_string().as_str())
+            .into_iter()
+            .flat_map(|data| data.passive_options.iter())
+            .filter_map(|option| self.option_delta(option))
+            .collect()
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+use std::{fs, io};
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use compact_str::CompactString;
+
+use crate::assets::{parse_json_slice, AssetSource, ASSET_DIR};
+use crate::error::AssetError;
+
+/// Optional user file next to `SupportBuffGroup.json`, its entries are added to
+/// the shipped ones and replace those with the same `uniqueGroup`, `"kind": null` removes the group.
+pub const SUPPORT_BUFF_OVERRIDES: &str = "SupportBuffGroup.user.json";
+
+/// The support contributions the meter tracks separately from `buffed_by` and `debuffed_by`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportBuffKind {
+    /// Attack power buffs, counted in `buffed_by_support`.
+    AttackPower,
+    /// Identity buffs, counted in `buffed_by_identity`.
+    Identity,
+    /// Damage taken debuffs on the target, counted in `debuffed_by_support`.
+    Brand,
+    /// Party buffs of hyper-awakening techniques, counted in `buffed_by_hat`.
+    HyperAwakening,
+}
+
+/// One `SupportBuffGroup.json` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportBuffGroup {
+    /// `uniqueGroup` of the buffs in `SkillBuff.json`.
+    pub unique_group: u32,
+    pub class_id: u32,
+    pub kind: SupportBuffKind,
+    pub description: CompactString,
+}
+
+/// One [`SUPPORT_BUFF_OVERRIDES`] entry, a group to add or replace, or with `"kind": null` one to remove.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportBuffOverride {
+    pub unique_group: u32,
+    #[serde(default)]
+    pub class_id: u32,
+    // `kind` has to be spelled out, a missing one is not taken as a removal
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub kind: Option<SupportBuffKind>,
+    #[serde(default)]
+    pub description: CompactString,
+}
+
+impl From<SupportBuffGroup> for SupportBuffOverride {
+    fn from(group: SupportBuffGroup) -> Self {
+        let SupportBuffGroup { unique_group, class_id, kind, description } = group;
+        Self { unique_group, class_id, kind: Some(kind), description }
+    }
+}
+
+/// Support buff groups by unique group, from `SupportBuffGroup.json` plus the user overrides.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "Vec<SupportBuffGroup>", into = "Vec<SupportBuffGroup>")]
+pub struct SupportBuffGroups {
+    groups: HashMap<u32, SupportBuffGroup>,
+    support_classes: HashSet<u32>,
+}
+
+impl From<Vec<SupportBuffGroup>> for SupportBuffGroups {
+    fn from(groups: Vec<SupportBuffGroup>) -> Self {
+        let mut registry = Self::default();
+        registry.apply_overrides(groups);
+        registry
+    }
+}
+
+impl From<SupportBuffGroups> for Vec<SupportBuffGroup> {
+    fn from(registry: SupportBuffGroups) -> Self {
+        let mut groups: Vec<_> = registry.groups.into_values().collect();
+        groups.sort_unstable_by_key(|group| group.unique_group);
+        groups
+    }
+}
+
+impl SupportBuffGroups {
+    /// The shipped `SupportBuffGroup.json` with the user overrides applied.
+    pub fn load<S: AssetSource>(source: &mut S) -> Result<Self, AssetError> {
+        source.load::<Self>("SupportBuffGroup.json")?.with_user_overrides()
+    }
+
+    /// Adds `overrides`, later entries replace earlier ones with the same unique group
+    /// and those without a kind remove it.
+    pub fn apply_overrides<O: Into<SupportBuffOverride>>(&mut self, overrides: impl IntoIterator<Item = O>) {
+        for entry in overrides {
+            let SupportBuffOverride { unique_group, class_id, kind, description } = entry.into();
+            match kind {
+                Some(kind) => {
+                    let group = SupportBuffGroup { unique_group, class_id, kind, description };
+                    self.groups.insert(unique_group, group);
+                }
+                None => {
+                    self.groups.remove(&unique_group);
+                }
+            }
+        }
+        // a replaced group can take the last mention of a class with it
+        self.support_classes = self.groups.values().map(|group| group.class_id).collect();
+    }
+
+    /// Applies [`SUPPORT_BUFF_OVERRIDES`] from [`ASSET_DIR`] when the file exists.
+    pub fn with_user_overrides(self) -> Result<Self, AssetError> {
+        self.with_user_overrides_from(ASSET_DIR)
+    }
+
+    /// Applies [`SUPPORT_BUFF_OVERRIDES`] from `asset_dir` when the file exists.
+    pub fn with_user_overrides_from(mut self, asset_dir: impl AsRef<Path>) -> Result<Self, AssetError> {
+        let path = asset_dir.as_ref().join(SUPPORT_BUFF_OVERRIDES);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(self),
+            Err(err) => return Err(AssetError::io(&path.display().to_string(), err)),
+        };
+
+        let overrides: Vec<SupportBuffOverride> = parse_json_slice(SUPPORT_BUFF_OVERRIDES, &bytes)?;
+        self.apply_overrides(overrides);
+        Ok(self)
+    }
+
+    pub fn get(&self, unique_group: u32) -> Option<&SupportBuffGroup> {
+        self.groups.get(&unique_group)
+    }
+
+    pub fn kind_of(&self, unique_group: u32) -> Option<SupportBuffKind> {
+        self.get(unique_group).map(|group| group.kind)
+    }
+
+    /// Unique groups of `kind`, sorted.
+    pub fn groups_of(&self, kind: SupportBuffKind) -> Vec<u32> {
+        let mut groups: Vec<u32> =
+            self.groups.values().filter(|group| group.kind == kind).map(|group| group.unique_group).collect();
+        groups.sort_unstable();
+        groups
+    }
+
+    /// Every class that has at least one group, these are the classes the meter treats as supports.
+    pub fn support_classes(&self) -> &HashSet<u32> {
+        &self.support_classes
+    }
+
+    pub fn is_support_class(&self, class_id: u32) -> bool {
+        self.support_classes.contains(&class_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SupportBuffGroup> {
+        self.groups.values()
+    }
+}
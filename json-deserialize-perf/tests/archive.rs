@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+
+use json_deserialize_perf::archive::{AssetArchive, MappedAssetArchive};
+use json_deserialize_perf::assets::{parse_json_str, AssetPreloader, AssetSource};
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::error::AssetError;
+
+const COMBAT_EFFECTS: &str = r#"{
+    "-5": { "effects": [{
+        "ratio": 10000,
+        "cooldown": 0,
+        "conditions": [{ "type": "hp_less", "actorType": "target", "arg": 30 }],
+        "actions": [{ "actionType": "modify_damage", "actorType": "self", "args": [1500] }]
+    }] },
+    "100": { "effects": [{
+        "ratio": 5000,
+        "cooldown": 0,
+        "conditions": [{ "type": "current_skill", "actorType": "self", "arg": 16300 }],
+        "actions": [{ "actionType": "modify_damage", "actorType": "self", "args": [2000] }]
+    }] }
+}"#;
+
+const SKILLS: &str = r#"{
+    "16300": { "id": 16300, "name": "Berserker Technique", "classId": 102 },
+    "21160": { "id": 21160, "name": "Heavenly Tune", "classId": 204 },
+    "21020": { "id": 21020, "name": "Sound Shock", "classId": 204 }
+}"#;
+
+const BUFFS: &str = r#"{
+    "101204": { "id": 101204, "name": "Heavenly Tune", "duration": 0, "uniqueGroup": 101204, "overlap": 0,
+        "category": "buff", "type": "attack_power_amplify", "target": "party", "perLevelData": {} }
+}"#;
+
+// the fixtures above for the tables the archive holds, the shipped files for everything else
+struct FixtureSource;
+
+impl AssetSource for FixtureSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        let fixture = match name {
+            "CombatEffect.json" => COMBAT_EFFECTS,
+            "Skill.json" => SKILLS,
+            "SkillBuff.json" => BUFFS,
+            "SkillEffect.json" | "Npc.json" => "{}",
+            _ => return ReaderSource.load(name),
+        };
+        parse_json_str(name, fixture)
+    }
+}
+
+fn archive_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("json-deserialize-perf-{}-{name}", std::process::id()))
+}
+
+fn write_archive(name: &str) -> PathBuf {
+    let path = archive_path(name);
+    let assets = AssetPreloader::from_source(&mut FixtureSource).unwrap();
+    AssetArchive::from_preloader(assets).write(&path).unwrap();
+    path
+}
+
+#[test]
+fn looks_up_written_tables() {
+    let path = write_archive("lookups.rkyv");
+    let archive = MappedAssetArchive::open(&path).unwrap();
+    let tables = archive.get();
+
+    assert_eq!(tables.skill_data.len(), 3);
+    assert_eq!(tables.skill_data.get(21160).unwrap().class_id, 204);
+    assert_eq!(tables.skill_data.get(16300).unwrap().name.as_deref(), Some("Berserker Technique"));
+    assert_eq!(tables.skill_buff_data.get(101204).unwrap().unique_group, 101204);
+    assert!(tables.skill_effect_data.is_empty());
+
+    // ids between, below and above the stored ones
+    assert!(tables.skill_data.get(21100).is_none());
+    assert!(tables.skill_data.get(0).is_none());
+    assert!(tables.skill_data.get(u32::MAX).is_none());
+    assert!(tables.skill_buff_data.get(1).is_none());
+    assert!(tables.npc_data.get(16300).is_none());
+
+    let ids: Vec<u32> = tables.skill_data.iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, [16300, 21020, 21160]);
+
+    drop(archive);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn looks_up_negative_combat_effect_ids() {
+    let path = write_archive("combat-effects.rkyv");
+    let archive = MappedAssetArchive::open(&path).unwrap();
+    let tables = archive.get();
+
+    let effect = tables.combat_effect(-5).unwrap();
+    assert_eq!(effect.effects[0].actions[0].args[0], 1500);
+    assert_eq!(tables.combat_effect(100).unwrap().effects[0].ratio, 5000);
+
+    assert!(tables.combat_effect(5).is_none());
+    assert!(tables.combat_effect(-100).is_none());
+    assert!(tables.combat_effect(-1).is_none());
+    // -5 is stored under its bit pattern, the unsigned lookup finds the same entry
+    assert!(tables.combat_effect_data.get(-5i32 as u32).is_some());
+
+    drop(archive);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn rejects_files_that_are_not_archives() {
+    let path = archive_path("garbage.rkyv");
+    std::fs::write(&path, b"not an archive").unwrap();
+
+    let err = MappedAssetArchive::open(&path).err().expect("invalid archive");
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(err, AssetError::Archive { .. }), "{err}");
+
+    let err = MappedAssetArchive::open(archive_path("missing.rkyv")).err().expect("missing archive");
+    assert!(matches!(err, AssetError::MissingFile { .. }), "{err}");
+}
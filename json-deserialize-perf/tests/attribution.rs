@@ -0,0 +1,186 @@
+use json_deserialize_perf::assets::AssetPreloader;
+use json_deserialize_perf::attribution::HitAttribution;
+use json_deserialize_perf::buff_flags::BuffFlagIndex;
+use json_deserialize_perf::encounter_builder::{DamageEvent, EncounterBuilder};
+use json_deserialize_perf::models::{DamageData, EncounterEntity, EntityType, HitFlag, HitOption};
+use json_deserialize_perf::support_buffs::{SupportBuffGroup, SupportBuffKind};
+
+// trimmed down SkillBuff.json entries, the required fields plus what attribution reads
+const BUFFS: &str = r#"{
+    "101204": { "id": 101204, "name": "Heavenly Tune", "duration": 0, "uniqueGroup": 101204, "overlap": 0,
+        "category": "buff", "type": "attack_power_amplify", "statusEffectValues": [1500],
+        "target": "party", "sourceSkills": [21160], "perLevelData": {} },
+    "211400": { "id": 211400, "name": "Serenade of Courage", "duration": 0, "uniqueGroup": 211400,
+        "overlap": 0, "category": "buff", "type": "skill_damage_amplify", "statusEffectValues": [0, 0, 1000],
+        "target": "party", "sourceSkills": [21140], "perLevelData": {} },
+    "210230": { "id": 210230, "name": "Sound Shock", "duration": 0, "uniqueGroup": 210230, "overlap": 0,
+        "category": "debuff", "type": "skill_damage_amplify", "statusEffectValues": [0, 0, 0, 0, 1000],
+        "target": "none", "sourceSkills": [21020], "perLevelData": {} },
+    "212305": { "id": 212305, "name": "Aria Amplify", "duration": 0, "uniqueGroup": 212305, "overlap": 0,
+        "category": "buff", "type": "skill_damage_amplify", "statusEffectValues": [1000],
+        "target": "party", "sourceSkills": [21300], "perLevelData": {} },
+    "160000": { "id": 160000, "name": "Berserker Technique", "duration": 0, "uniqueGroup": 160000,
+        "overlap": 0, "category": "buff", "type": "skill_damage_amplify", "statusEffectValues": [1000],
+        "target": "party", "sourceSkills": [16300], "perLevelData": {} },
+    "500153": { "id": 500153, "name": "Whirlwind Grenade", "duration": 0, "uniqueGroup": 500153, "overlap": 0,
+        "category": "debuff", "type": "skill_damage_amplify", "statusEffectValues": [1000],
+        "target": "none", "perLevelData": {} },
+    "160500": { "id": 160500, "name": "Bloody Rush", "duration": 0, "uniqueGroup": 160500, "overlap": 0,
+        "category": "buff", "type": "skill_damage_amplify", "statusEffectValues": [1000],
+        "target": "self", "sourceSkills": [16050], "perLevelData": {} },
+    "990230": { "id": 990230, "name": "New Support Brand", "duration": 0, "uniqueGroup": 990230,
+        "overlap": 0, "category": "debuff", "type": "skill_damage_amplify", "statusEffectValues": [1000],
+        "target": "none", "sourceSkills": [99020], "perLevelData": {} },
+    "990300": { "id": 990300, "name": "New Support Technique", "duration": 0, "uniqueGroup": 990300,
+        "overlap": 0, "category": "buff", "type": "skill_damage_amplify", "statusEffectValues": [1000],
+        "target": "party", "sourceSkills": [99300], "perLevelData": {} }
+}"#;
+
+const SKILLS: &str = r#"{
+    "21160": { "id": 21160, "name": "Heavenly Tune", "classId": 204 },
+    "21140": { "id": 21140, "name": "Serenade of Courage", "classId": 204 },
+    "21020": { "id": 21020, "name": "Sound Shock", "classId": 204 },
+    "21300": { "id": 21300, "name": "Aria", "classId": 204, "isHyperAwakening": true },
+    "16300": { "id": 16300, "name": "Berserker Technique", "classId": 102, "isHyperAwakening": true },
+    "16050": { "id": 16050, "name": "Bloody Rush", "classId": 102 },
+    "99020": { "id": 99020, "name": "New Support Brand", "classId": 999 },
+    "99300": { "id": 99300, "name": "New Support Technique", "classId": 999, "isHyperAwakening": true }
+}"#;
+
+fn assets() -> AssetPreloader {
+    let mut assets = AssetPreloader::new().unwrap();
+    assets.skill_buff_data = serde_json::from_str(BUFFS).unwrap();
+    assets.skill_data = serde_json::from_str(SKILLS).unwrap();
+    assets.buff_flags = BuffFlagIndex::build(&assets.skill_buff_data, &assets.stat_type_map);
+    assets
+}
+
+#[test]
+fn classifies_support_contributions() {
+    let assets = assets();
+    let attributor = assets.attributor();
+
+    assert_eq!(attributor.kind_of(101204), Some(SupportBuffKind::AttackPower));
+    assert_eq!(attributor.kind_of(211400), Some(SupportBuffKind::Identity));
+    assert_eq!(attributor.kind_of(210230), Some(SupportBuffKind::Brand));
+    assert_eq!(attributor.kind_of(212305), Some(SupportBuffKind::HyperAwakening));
+
+    // a dps hyper-awakening, a battle item debuff, a self buff and an unknown id
+    assert_eq!(attributor.kind_of(160000), None);
+    assert_eq!(attributor.kind_of(500153), None);
+    assert_eq!(attributor.kind_of(160500), None);
+    assert_eq!(attributor.kind_of(1), None);
+}
+
+#[test]
+fn override_only_supports_get_brand_and_hat_attribution() {
+    let mut assets = assets();
+    assert_eq!(assets.attributor().kind_of(990230), None);
+    assert_eq!(assets.attributor().kind_of(990300), None);
+
+    // the class only shows up through an override entry for its identity
+    assets.support_buff_groups.apply_overrides([SupportBuffGroup {
+        unique_group: 990400,
+        class_id: 999,
+        kind: SupportBuffKind::Identity,
+        description: "New support identity".into(),
+    }]);
+    let attributor = assets.attributor();
+
+    assert_eq!(attributor.kind_of(990230), Some(SupportBuffKind::Brand));
+    assert_eq!(attributor.kind_of(990300), Some(SupportBuffKind::HyperAwakening));
+
+    let attribution = attributor.attribute(&[990300], &[990230]);
+    assert!(attribution.brand);
+    assert!(attribution.hat);
+}
+
+#[test]
+fn attributes_a_hit() {
+    let assets = assets();
+    let buffs = [212305, 160500, 101204, 1, 160500];
+    let attribution = assets.attributor().attribute(&buffs, &[500153, 210230, 2]);
+
+    assert_eq!(
+        attribution,
+        HitAttribution {
+            buffed_by: vec![101204, 160500, 212305],
+            debuffed_by: vec![210230, 500153],
+            support: true,
+            identity: false,
+            brand: true,
+            hat: true,
+        }
+    );
+}
+
+#[test]
+fn contributions_only_count_on_their_side() {
+    let assets = assets();
+    let attribution = assets.attributor().attribute(&[210230], &[101204, 211400]);
+
+    assert!(!attribution.brand);
+    assert!(!attribution.support);
+    assert!(!attribution.identity);
+    assert_eq!(attribution.buffed_by, [210230]);
+}
+
+fn hit(damage: i64, at: i64) -> DamageEvent {
+    DamageEvent {
+        source_id: 1,
+        target_id: 2,
+        damage: DamageData {
+            skill_id: 16050,
+            skill_effect_id: 0,
+            damage,
+            shield_damage: None,
+            modifier: 0,
+            target_current_hp: 0,
+            target_max_hp: 0,
+            damage_attribute: None,
+            damage_type: 0,
+        },
+        hit_flag: HitFlag::NORMAL,
+        hit_option: HitOption::NONE,
+        timestamp: 1_000 + at,
+    }
+}
+
+#[test]
+fn builder_fills_attribution_counters() {
+    let assets = assets();
+    let attributor = assets.attributor();
+
+    let entity =
+        |id, name: &str, entity_type| EncounterEntity { id, name: name.into(), entity_type, ..Default::default() };
+    let mut builder = EncounterBuilder::new();
+    builder.add_entity(entity(1, "Zerk", EntityType::PLAYER));
+    builder.add_entity(entity(2, "Boss", EntityType::BOSS));
+
+    builder.ingest_attributed(hit(1000, 0), &attributor.attribute(&[101204, 211400], &[210230]));
+    builder.ingest_attributed(hit(500, 100), &attributor.attribute(&[101204], &[]));
+    builder.ingest(hit(200, 200));
+
+    let encounter = builder.finish();
+    let zerk = &encounter.entities["Zerk"];
+    let skill = &zerk.skills[&16050];
+
+    assert_eq!(skill.buffed_by[&101204], 1500);
+    assert_eq!(skill.buffed_by[&211400], 1000);
+    assert_eq!(skill.debuffed_by[&210230], 1000);
+    assert_eq!(skill.buffed_by_support, 1500);
+    assert_eq!(skill.buffed_by_identity, 1000);
+    assert_eq!(skill.debuffed_by_support, 1000);
+    assert_eq!(skill.buffed_by_hat, 0);
+
+    assert_eq!(zerk.damage_stats.buffed_by[&101204], 1500);
+    assert_eq!(zerk.damage_stats.buffed_by_support, 1500);
+    assert_eq!(zerk.damage_stats.buffed_by_identity, 1000);
+    assert_eq!(zerk.damage_stats.debuffed_by_support, 1000);
+
+    let hits = &skill.skill_cast_log[0].hits;
+    assert_eq!(hits[0].buffed_by, [101204, 211400]);
+    assert_eq!(hits[0].debuffed_by, [210230]);
+    assert_eq!(hits[1].buffed_by, [101204]);
+    assert!(hits[2].buffed_by.is_empty());
+}
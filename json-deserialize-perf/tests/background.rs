@@ -0,0 +1,109 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use serde::de::DeserializeOwned;
+
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::background::BackgroundPreloader;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::error::AssetError;
+
+// reads the shipped files, but stops before `gate` until the test lets it through
+struct GatedSource {
+    gate: &'static str,
+    reached: Sender<()>,
+    release: Receiver<()>,
+}
+
+impl AssetSource for GatedSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        if name == self.gate {
+            self.reached.send(()).unwrap();
+            self.release.recv().unwrap();
+        }
+        ReaderSource.load(name)
+    }
+}
+
+fn gated(gate: &'static str) -> (BackgroundPreloader, Receiver<()>, Sender<()>) {
+    let (reached, on_reached) = channel();
+    let (release, on_release) = channel();
+    let preloader = BackgroundPreloader::spawn(GatedSource { gate, reached, release: on_release });
+    (preloader, on_reached, release)
+}
+
+// fails on `missing` as if the file was not there
+struct FailingSource {
+    missing: &'static str,
+}
+
+impl AssetSource for FailingSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        if name == self.missing {
+            return Err(AssetError::MissingFile { path: name.into() });
+        }
+        ReaderSource.load(name)
+    }
+}
+
+#[test]
+fn tables_fill_in_one_by_one() {
+    let (preloader, reached, release) = gated("CombatEffect.json");
+    reached.recv().unwrap();
+
+    let tables = preloader.tables();
+    assert!(tables.skill_data().is_some());
+    assert!(tables.skill_buff_data().is_some());
+    assert!(tables.skill_effect_data().is_some());
+    assert!(tables.skill_index().is_some());
+    assert!(tables.support_buff_groups().is_some());
+    assert!(tables.combat_effect_data().is_none());
+    assert!(tables.buff_flags().is_none());
+    assert!(tables.npc_data().is_none());
+    assert!(tables.item_sets().is_none());
+
+    release.send(()).unwrap();
+    let assets = preloader.wait().unwrap();
+    assert!(!assets.class_registry.is_empty());
+}
+
+#[test]
+fn try_get_hands_back_the_loader_while_loading() {
+    let (preloader, reached, release) = gated("Npc.json");
+    reached.recv().unwrap();
+
+    assert!(!preloader.is_finished());
+    let mut preloader = preloader.try_get().err().expect("still loading");
+    assert!(preloader.tables().esther_data().is_some());
+
+    release.send(()).unwrap();
+    let assets = loop {
+        match preloader.try_get() {
+            Ok(assets) => break assets,
+            Err(loading) => {
+                preloader = loading;
+                std::thread::yield_now();
+            }
+        }
+    };
+    assert!(assets.is_ok());
+}
+
+#[test]
+fn wait_surfaces_load_errors() {
+    let preloader = BackgroundPreloader::spawn(FailingSource { missing: "Npc.json" });
+
+    let err = preloader.wait().err().expect("load error");
+    assert!(matches!(&err, AssetError::MissingFile { path } if path == "Npc.json"), "{err}");
+}
+
+#[test]
+fn tables_loaded_before_an_error_stay_readable() {
+    let preloader = BackgroundPreloader::spawn(FailingSource { missing: "GemSkillGroup.json" });
+    while !preloader.is_finished() {
+        std::thread::yield_now();
+    }
+
+    assert!(preloader.tables().npc_data().is_some());
+    assert!(preloader.tables().gem_skill_groups().is_none());
+    assert!(preloader.try_get().ok().expect("finished").is_err());
+}
@@ -0,0 +1,148 @@
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+
+use json_deserialize_perf::assets::{asset_path, parse_json_str, AssetSource};
+use json_deserialize_perf::deser_borrowed::{AssetBuffers, BorrowedAssets, OwnedAssets, BORROWED_FILES};
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::error::AssetError;
+
+const ABILITY: &str = r#"{
+    "107": { "id": 107, "name": "Disrespect", "icon": "ability_107.png", "levels": {},
+        "featureType": "ruthless", "isEngrave": true, "isPenalty": false },
+    "900": { "id": 900, "name": "Tab\tand \"quotes\"", "icon": "ability_900.png", "levels": {},
+        "featureType": "ruthless", "isEngrave": false, "isPenalty": true }
+}"#;
+
+const SKILL_BUFF: &str = r#"{
+    "101204": { "id": 101204, "name": "Heavenly Tune", "desc": "Atk. Power +15%\nfor 8s", "duration": 0,
+        "uniqueGroup": 101204, "overlap": 0, "category": "buff", "type": "attack_power_amplify",
+        "target": "party", "perLevelData": {} }
+}"#;
+
+const SKILL: &str = r#"{
+    "16300": { "id": 16300, "name": "Berserker\u2019s Technique", "classId": 102, "type": 3 },
+    "21160": { "id": 21160, "name": "Heavenly Tune", "classId": 204, "type": "normal" }
+}"#;
+
+const SKILL_EFFECT: &str = r#"{
+    "1": { "id": 1, "comment": "line\nbreak", "itemName": "Whirlwind Grenade", "values": [] }
+}"#;
+
+const NPC: &str = r#"{
+    "480005": { "id": 480005, "name": "Thaemine, the Lightqueller", "grade": "boss", "type": "monster" },
+    "480006": { "id": 480006, "name": "Thaemine \\ Prokel", "grade": "boss", "type": "monster" }
+}"#;
+
+fn fixture(name: &str) -> Result<&'static str, AssetError> {
+    Ok(match name {
+        "Ability.json" => ABILITY,
+        "SkillBuff.json" => SKILL_BUFF,
+        "Skill.json" => SKILL,
+        "SkillEffect.json" => SKILL_EFFECT,
+        "Npc.json" => NPC,
+        _ => return Err(AssetError::MissingFile { path: name.into() }),
+    })
+}
+
+struct FixtureSource;
+
+impl AssetSource for FixtureSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        parse_json_str(name, fixture(name)?)
+    }
+}
+
+fn sorted<K: Ord + Copy, V>(map: impl IntoIterator<Item = (K, V)>) -> Vec<K> {
+    let mut ids: Vec<K> = map.into_iter().map(|(id, _)| id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+fn assert_agree(borrowed: &BorrowedAssets, owned: &OwnedAssets) {
+    let borrowed_ids = sorted(borrowed.engraving_data.keys().map(|id| (*id, ())));
+    assert_eq!(borrowed_ids, sorted(owned.engraving_data.keys().map(|id| (*id, ()))));
+    for (id, engraving) in &owned.engraving_data {
+        let other = &borrowed.engraving_data[id];
+        assert_eq!(other.name.as_deref(), engraving.name.as_deref(), "{id}");
+        assert_eq!(other.icon.as_deref(), engraving.icon.as_deref(), "{id}");
+    }
+
+    assert_eq!(borrowed.skill_buff_data.len(), owned.skill_buff_data.len());
+    for (id, buff) in &owned.skill_buff_data {
+        let other = &borrowed.skill_buff_data[id];
+        assert_eq!(other.name.as_deref(), buff.name.as_deref(), "{id}");
+        assert_eq!(other.desc.as_deref(), buff.desc.as_deref(), "{id}");
+        assert_eq!(other.buff_type, buff.buff_type, "{id}");
+    }
+
+    assert_eq!(borrowed.skill_data.len(), owned.skill_data.len());
+    for (id, skill) in &owned.skill_data {
+        let other = &borrowed.skill_data[id];
+        assert_eq!(other.name.as_deref(), skill.name.as_deref(), "{id}");
+        assert_eq!(other.skill_type, skill.skill_type, "{id}");
+        assert_eq!(other.class_id, skill.class_id, "{id}");
+    }
+
+    assert_eq!(borrowed.skill_effect_data.len(), owned.skill_effect_data.len());
+    for (id, effect) in &owned.skill_effect_data {
+        let other = &borrowed.skill_effect_data[id];
+        assert_eq!(other.comment, effect.comment.as_str(), "{id}");
+        assert_eq!(other.item_name.as_deref(), effect.item_name.as_deref(), "{id}");
+    }
+
+    assert_eq!(borrowed.npc_data.len(), owned.npc_data.len());
+    for (id, npc) in &owned.npc_data {
+        let other = &borrowed.npc_data[id];
+        assert_eq!(other.name.as_deref(), npc.name.as_deref(), "{id}");
+        assert_eq!(other.grade.as_ref(), npc.grade.as_str(), "{id}");
+        assert_eq!(other.npc_type.as_ref(), npc.npc_type.as_str(), "{id}");
+    }
+}
+
+#[test]
+fn borrowed_and_owned_agree_on_fixtures() {
+    let borrowed = BorrowedAssets::parse(fixture).unwrap();
+    let owned = OwnedAssets::load(&mut FixtureSource).unwrap();
+    assert_agree(&borrowed, &owned);
+}
+
+#[test]
+fn escaped_strings_fall_back_to_owned() {
+    let borrowed = BorrowedAssets::parse(fixture).unwrap();
+
+    let plain = &borrowed.engraving_data[&107];
+    assert!(matches!(plain.name, Some(Cow::Borrowed("Disrespect"))));
+    let escaped = &borrowed.engraving_data[&900];
+    assert!(matches!(&escaped.name, Some(Cow::Owned(name)) if name == "Tab\tand \"quotes\""));
+    // only the escaped field is copied
+    #[cfg(not(feature = "slim-engravings"))]
+    assert!(matches!(escaped.feature_type, Cow::Borrowed("ruthless")));
+
+    let skill = &borrowed.skill_data[&16300];
+    assert!(matches!(&skill.name, Some(Cow::Owned(name)) if name == "Berserker\u{2019}s Technique"));
+    let comment = &borrowed.skill_effect_data[&1].comment;
+    assert!(matches!(comment, Cow::Owned(comment) if comment == "line\nbreak"));
+    let npc = &borrowed.npc_data[&480006];
+    assert!(matches!(&npc.name, Some(Cow::Owned(name)) if name == "Thaemine \\ Prokel"));
+    assert!(matches!(borrowed.npc_data[&480005].grade, Cow::Borrowed("boss")));
+}
+
+#[test]
+fn shipped_files_agree_through_every_loader() {
+    let owned = OwnedAssets::load(&mut ReaderSource).unwrap();
+
+    let buffers = AssetBuffers::read().unwrap();
+    assert_agree(&buffers.parse().unwrap(), &owned);
+    assert_agree(&BorrowedAssets::embedded().unwrap(), &owned);
+}
+
+#[test]
+fn missing_buffers_are_reported() {
+    let without_npcs = |name| match name {
+        "Npc.json" => Err(AssetError::MissingFile { path: asset_path(name) }),
+        name => fixture(name),
+    };
+    let err = BorrowedAssets::parse(without_npcs).err().expect("missing file");
+    assert!(matches!(&err, AssetError::MissingFile { path } if path.ends_with("Npc.json")), "{err}");
+    assert!(BORROWED_FILES.contains(&"Npc.json"));
+}
@@ -0,0 +1,131 @@
+use hashbrown::HashMap;
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::buff_flags::BuffFlagIndex;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::models::{SkillBuffData, StatusEffectBuffTypeFlags};
+
+// trimmed down SkillBuff.json entries, the required fields plus what the classification reads
+const SAMPLE: &str = r#"{
+    "210230": { "id": 210230, "name": "Sound Shock", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "debuff", "type": "skill_damage_amplify",
+        "statusEffectValues": [0, 0, 0, 0, 1000], "target": "none", "perLevelData": {} },
+    "211400": { "id": 211400, "name": "Serenade of Courage", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "stat",
+        "target": "party", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "attack_power_rate", "keyIndex": 0, "value": 1500 } ] } } },
+    "211606": { "id": 211606, "name": "Heavenly Tune", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "attack_power_amplify",
+        "statusEffectValues": [1500], "target": "party", "perLevelData": {} },
+    "212305": { "id": 212305, "name": "Zero Amplify", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "skill_damage_amplify",
+        "statusEffectValues": [0, 0, 0], "target": "party", "perLevelData": {} },
+    "240500": { "id": 240500, "name": "Shield of Grace", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "shield",
+        "target": "party", "perLevelData": {} },
+    "360506": { "id": 360506, "name": "Holy Protection", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "stat",
+        "target": "party", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "physical_inc_rate", "keyIndex": 0, "value": -2000 },
+            { "type": "stat", "keyStat": "magical_inc_rate", "keyIndex": 0, "value": -2000 } ] } } },
+    "362600": { "id": 362600, "name": "Blessed Aura", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "stat",
+        "target": "party", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "critical_hit_rate", "keyIndex": 0, "value": 1000 },
+            { "type": "skill_critical_damage", "keyStat": "none", "keyIndex": 0, "value": 800 } ] } } },
+    "500153": { "id": 500153, "name": "Whirlwind Grenade", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "debuff", "type": "stat",
+        "target": "none", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "def", "keyIndex": 0, "value": -1200 },
+            { "type": "stat", "keyStat": "move_speed_rate", "keyIndex": 0, "value": -3000 } ] } } },
+    "501100": { "id": 501100, "name": "Weakness Exposure", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "debuff", "type": "stat",
+        "target": "none", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "attack_power_rate", "keyIndex": 0, "value": -1000 } ] } } },
+    "701001": { "id": 701001, "name": "Swiftness Food", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "stat",
+        "target": "self", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "rapidity", "keyIndex": 0, "value": 50 },
+            { "type": "stat", "keyStat": "combat_mp_recovery_rate", "keyIndex": 0, "value": 500 },
+            { "type": "none", "keyStat": "none", "keyIndex": 0, "value": 0 } ] } } },
+    "701002": { "id": 701002, "name": "Stagger Tonic", "duration": 0, "uniqueGroup": 0, "overlap": 0,
+        "category": "buff", "type": "change_ai_point",
+        "target": "self", "perLevelData": { "1": { "passiveOptions": [
+            { "type": "stat", "keyStat": "max_hp_x", "keyIndex": 0, "value": 3000 } ] } } }
+}"#;
+
+const SNAPSHOT: &str = "\
+210230 Sound Shock: DMG
+211400 Serenade of Courage: DMG
+211606 Heavenly Tune: DMG
+212305 Zero Amplify: NONE
+240500 Shield of Grace: SHIELD
+360506 Holy Protection: DEFENSE
+362600 Blessed Aura: DMG | CRIT
+500153 Whirlwind Grenade: DMG | MOVESPEED
+501100 Weakness Exposure: DEFENSE
+701001 Swiftness Food: ATKSPEED | RESOURCE | COOLDOWN
+701002 Stagger Tonic: HP | STAGGER
+";
+
+fn sample() -> HashMap<u32, SkillBuffData> {
+    serde_json::from_str(SAMPLE).unwrap()
+}
+
+fn index(buffs: &HashMap<u32, SkillBuffData>) -> BuffFlagIndex {
+    let stat_type_map: HashMap<String, u32> = ReaderSource.load("StatType.json").unwrap();
+    BuffFlagIndex::build(buffs, &stat_type_map)
+}
+
+fn flag_names(flags: StatusEffectBuffTypeFlags) -> String {
+    if flags.is_empty() {
+        return "NONE".into();
+    }
+    flags.iter_names().map(|(name, _)| name).collect::<Vec<_>>().join(" | ")
+}
+
+#[test]
+fn classifies_sample_buffs() {
+    let buffs = sample();
+    let index = index(&buffs);
+
+    let mut ids: Vec<_> = buffs.keys().copied().collect();
+    ids.sort_unstable();
+
+    let snapshot: String = ids
+        .iter()
+        .map(|id| {
+            let name = buffs[id].name.as_deref().unwrap_or_default();
+            format!("{id} {name}: {}\n", flag_names(index.get(*id).unwrap()))
+        })
+        .collect();
+
+    assert_eq!(snapshot, SNAPSHOT);
+}
+
+#[test]
+fn queries_buffs_by_flags() {
+    let buffs = sample();
+    let index = index(&buffs);
+
+    let mut damage: Vec<_> = index.buffs_with_flags(StatusEffectBuffTypeFlags::DMG).collect();
+    damage.sort_unstable();
+    assert_eq!(damage, [210230, 211400, 211606, 362600, 500153]);
+
+    let crit_damage: Vec<_> =
+        index.buffs_with_flags(StatusEffectBuffTypeFlags::DMG | StatusEffectBuffTypeFlags::CRIT).collect();
+    assert_eq!(crit_damage, [362600]);
+
+    assert_eq!(index.buffs_with_flags(StatusEffectBuffTypeFlags::NONE).count(), buffs.len());
+    assert_eq!(index.get(1), None);
+}
+
+#[test]
+fn survives_the_cache_encoding() {
+    let buffs = sample();
+    let index = index(&buffs);
+
+    let bytes = postcard::to_stdvec(&index).unwrap();
+    let cached: BuffFlagIndex = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(cached.len(), index.len());
+    assert!(buffs.keys().all(|id| cached.get(*id) == index.get(*id)));
+}
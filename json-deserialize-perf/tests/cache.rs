@@ -0,0 +1,191 @@
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use json_deserialize_perf::assets::{AssetPreloader, ASSET_FILES};
+use json_deserialize_perf::cache::AssetCache;
+use json_deserialize_perf::error::AssetError;
+use json_deserialize_perf::models::*;
+use json_deserialize_perf::support_buffs::SUPPORT_BUFF_OVERRIDES;
+
+// `type` as a number and as a string, plus string enum values the models do not know
+const SKILLS: &str = r#"{
+    "16300": { "id": 16300, "name": "Berserker Technique", "classId": 102, "type": 3 },
+    "21160": { "id": 21160, "name": "Heavenly Tune", "classId": 204, "type": "normal" }
+}"#;
+
+const BUFFS: &str = r#"{
+    "101204": { "id": 101204, "name": "Heavenly Tune", "duration": 0, "uniqueGroup": 101204, "overlap": 0,
+        "category": "buff", "type": "attack_power_amplify", "target": "party", "perLevelData": {} },
+    "990001": { "id": 990001, "duration": 0, "uniqueGroup": 990001, "overlap": 0,
+        "category": "some_new_category", "type": 42, "target": "raid",
+        "buffCategory": "some_new_buff_category", "perLevelData": {} }
+}"#;
+
+const COMBAT_EFFECTS: &str = r#"{
+    "-5": { "effects": [{
+        "ratio": 10000,
+        "cooldown": 0,
+        "conditions": [{ "type": "some_new_condition", "actorType": "someone_new", "arg": 7 }],
+        "actions": [{ "actionType": "some_new_action", "actorType": "target", "args": [1, 2] }]
+    }] },
+    "100": { "effects": [{
+        "ratio": 10000,
+        "cooldown": 0,
+        "conditions": [{ "type": "current_skill", "actorType": "self", "arg": 16300 }],
+        "actions": [{ "actionType": "modify_damage", "actorType": "self", "args": [2000] }]
+    }] }
+}"#;
+
+fn cache_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("json-deserialize-perf-{}-{name}", std::process::id()))
+}
+
+fn assets() -> AssetPreloader {
+    let mut assets = AssetPreloader::new().unwrap();
+    assets.skill_data = serde_json::from_str(SKILLS).unwrap();
+    assets.skill_buff_data = serde_json::from_str(BUFFS).unwrap();
+    assets.combat_effect_data = serde_json::from_str(COMBAT_EFFECTS).unwrap();
+    assets
+}
+
+#[test]
+fn round_trips_tables_with_unknown_values() {
+    let cache = AssetCache::new(cache_path("round-trip.cache"));
+    cache.store(&assets()).unwrap();
+    let assets = cache.load().expect("fresh cache");
+    std::fs::remove_file(cache.path()).unwrap();
+
+    // postcard is not human readable, the normalized strings are read back as they were stored
+    assert_eq!(assets.skill_data[&16300].skill_type, "3");
+    assert_eq!(assets.skill_data[&21160].skill_type, "normal");
+    assert_eq!(assets.skill_data[&21160].class_id, 204);
+
+    let known = &assets.skill_buff_data[&101204];
+    assert_eq!(known.buff_type, SkillBuffType::AttackPowerAmplify);
+    assert_eq!(known.target, SkillBuffTarget::Party);
+
+    let unknown = &assets.skill_buff_data[&990001];
+    assert_eq!(unknown.category, StatusEffectCategory::Unknown("some_new_category".into()));
+    assert_eq!(unknown.buff_type, SkillBuffType::Unknown("42".into()));
+    assert_eq!(unknown.target, SkillBuffTarget::Unknown("raid".into()));
+    let buff_category = StatusEffectBuffCategory::Unknown("some_new_buff_category".into());
+    assert_eq!(unknown.buff_category, Some(buff_category));
+
+    let effect = &assets.combat_effect_data[&-5].effects[0];
+    let condition_type = CombatEffectConditionType::Unknown("some_new_condition".into());
+    assert_eq!(effect.conditions[0].condition_type, condition_type);
+    assert_eq!(effect.conditions[0].actor_type, CombatEffectActor::Unknown("someone_new".into()));
+    assert_eq!(effect.actions[0].action_type, CombatEffectActionType::Unknown("some_new_action".into()));
+    assert_eq!(effect.actions[0].actor_type, CombatEffectActor::Target);
+    assert_eq!(effect.actions[0].args, [1, 2]);
+
+    let effect = &assets.combat_effect_data[&100].effects[0];
+    assert_eq!(effect.conditions[0].condition_type, CombatEffectConditionType::CurrentSkill);
+    assert_eq!(effect.actions[0].action_type, CombatEffectActionType::ModifyDamage);
+}
+
+#[test]
+fn temp_file_does_not_replace_a_tmp_cache() {
+    // `with_extension("tmp")` would have made the temp file the cache itself, losing the atomic rename
+    let cache = AssetCache::new(cache_path("assets.tmp"));
+    cache.store(&assets()).unwrap();
+
+    let assets = cache.load().expect("fresh cache");
+    std::fs::remove_file(cache.path()).unwrap();
+    assert_eq!(assets.skill_data.len(), 2);
+}
+
+#[test]
+fn write_errors_name_the_file_that_failed() {
+    let cache = AssetCache::new(cache_path("missing-dir").join("assets.cache"));
+
+    let err = cache.store(&assets()).unwrap_err();
+    assert!(matches!(&err, AssetError::Io { path, .. } if path.ends_with("assets.cache.tmp")), "{err}");
+}
+
+// an asset directory of stand-in files, the header only looks at their size and mtime
+fn asset_dir(name: &str) -> PathBuf {
+    let dir = cache_path(name);
+    fs::create_dir_all(&dir).unwrap();
+    for file in ASSET_FILES {
+        fs::write(dir.join(file), "{}").unwrap();
+    }
+    dir
+}
+
+fn stale_after(name: &str, change: impl FnOnce(&PathBuf)) -> bool {
+    let dir = asset_dir(name);
+    let cache = AssetCache::new(dir.join("assets.cache")).with_asset_dir(&dir);
+    cache.store(&assets()).unwrap();
+    assert!(cache.load().is_some(), "fresh cache");
+
+    change(&dir);
+    let stale = cache.load().is_none();
+    fs::remove_dir_all(&dir).unwrap();
+    stale
+}
+
+#[test]
+fn untouched_sources_keep_the_cache() {
+    assert!(!stale_after("untouched", |_| {}));
+}
+
+#[test]
+fn size_change_invalidates() {
+    assert!(stale_after("size", |dir| fs::write(dir.join("Skill.json"), "{ }").unwrap()));
+}
+
+#[test]
+fn mtime_change_invalidates() {
+    assert!(stale_after("mtime", |dir| {
+        let file = File::options().write(true).open(dir.join("Npc.json")).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+    }));
+}
+
+#[test]
+fn adding_or_removing_user_overrides_invalidates() {
+    assert!(stale_after("add-overrides", |dir| fs::write(dir.join(SUPPORT_BUFF_OVERRIDES), "[]").unwrap()));
+
+    let dir = asset_dir("remove-overrides");
+    fs::write(dir.join(SUPPORT_BUFF_OVERRIDES), "[]").unwrap();
+    let cache = AssetCache::new(dir.join("assets.cache")).with_asset_dir(&dir);
+    cache.store(&assets()).unwrap();
+    assert!(cache.load().is_some(), "fresh cache");
+
+    fs::remove_file(dir.join(SUPPORT_BUFF_OVERRIDES)).unwrap();
+    assert!(cache.load().is_none());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn stale_cache_is_rebuilt_and_rewritten() {
+    let dir = asset_dir("rebuild");
+    let cache = AssetCache::new(dir.join("assets.cache")).with_asset_dir(&dir);
+    let mut builds = 0;
+    let mut build = || {
+        builds += 1;
+        Ok(assets())
+    };
+
+    cache.load_or_build_with(&mut build).unwrap();
+    cache.load_or_build_with(&mut build).unwrap();
+    fs::write(dir.join("Skill.json"), "{ }").unwrap();
+    let assets = cache.load_or_build_with(&mut build).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(builds, 2);
+    assert_eq!(assets.skill_data.len(), 2);
+}
+
+#[test]
+fn missing_sources_are_reported() {
+    let dir = asset_dir("missing-source");
+    fs::remove_file(dir.join("Npc.json")).unwrap();
+    let cache = AssetCache::new(dir.join("assets.cache")).with_asset_dir(&dir);
+
+    let err = cache.store(&assets()).unwrap_err();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(&err, AssetError::MissingFile { path } if path.ends_with("Npc.json")), "{err}");
+}
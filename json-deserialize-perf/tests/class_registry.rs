@@ -0,0 +1,76 @@
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::classes::ClassRegistry;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::support_buffs::SupportBuffGroups;
+
+fn registry() -> ClassRegistry {
+    ReaderSource.load("PCData.json").unwrap()
+}
+
+#[test]
+fn looks_up_names_and_ids() {
+    let registry = registry();
+
+    assert_eq!(registry.name_of(204), Some("Bard"));
+    assert_eq!(registry.name_of(101), Some("Warrior (Male)"));
+    assert_eq!(registry.id_of("Bard"), Some(204));
+    assert_eq!(registry.id_of("Gunner (Female)"), Some(511));
+    assert_eq!(registry.name_of(999), None);
+    assert_eq!(registry.id_of("bard"), None);
+}
+
+#[test]
+fn skips_enum_null_placeholder() {
+    let registry = registry();
+
+    assert_eq!(registry.name_of(0), None);
+    assert_eq!(registry.id_of("ENUMNULL"), None);
+    assert_eq!(registry.len(), 37);
+}
+
+#[test]
+fn every_name_maps_back_to_its_id() {
+    let registry = registry();
+
+    for (id, name) in registry.iter() {
+        assert_eq!(registry.id_of(name), Some(id), "{name}");
+    }
+}
+
+#[test]
+fn relates_base_and_advanced_classes() {
+    let registry = registry();
+
+    assert!(registry.is_base_class(101));
+    assert!(!registry.is_base_class(102));
+    assert_eq!(registry.advanced_classes_of(101), vec![102, 103, 104, 105]);
+    assert_eq!(registry.advanced_classes_of(111), vec![112, 113]);
+    assert_eq!(registry.advanced_classes_of(511), vec![512]);
+    assert_eq!(registry.advanced_classes_of(601), vec![602, 603, 604]);
+    assert!(registry.advanced_classes_of(102).is_empty());
+
+    assert_eq!(registry.base_class_of(105), Some(101));
+    assert_eq!(registry.base_class_of(113), Some(111));
+    assert_eq!(registry.base_class_of(204), Some(201));
+    assert_eq!(registry.base_class_of(101), None);
+    assert_eq!(registry.base_class_of(999), None);
+
+    for (id, _) in registry.iter().filter(|(id, _)| !registry.is_base_class(*id)) {
+        let base = registry.base_class_of(id).unwrap();
+        assert!(registry.advanced_classes_of(base).contains(&id), "{id}");
+    }
+}
+
+#[test]
+fn detects_support_classes() {
+    let registry = registry();
+    let groups: SupportBuffGroups = ReaderSource.load("SupportBuffGroup.json").unwrap();
+
+    let mut supports: Vec<_> =
+        registry.iter().filter(|(id, _)| registry.is_support(*id, &groups)).map(|(_, name)| name).collect();
+    supports.sort_unstable();
+
+    assert_eq!(supports, ["Artist", "Bard", "Paladin", "Valkyrie"]);
+    assert!(!registry.is_support(102, &groups));
+    assert!(!registry.is_support(999, &groups));
+}
@@ -0,0 +1,118 @@
+use json_deserialize_perf::combat_effects::{ActorState, CombatEffectActionKind, CombatEffectConditionKind, HitContext};
+use json_deserialize_perf::models::{CombatEffectActor, CombatEffectData, HitFlag, HitOption};
+
+const EFFECT: &str = r#"{
+    "effects": [
+        {
+            "ratio": 10000,
+            "cooldown": 0,
+            "conditions": [
+                { "type": "current_skill", "actorType": "self", "arg": 16300 },
+                { "type": "target_count", "actorType": "self", "arg": 1 }
+            ],
+            "actions": [{ "actionType": "modify_damage", "actorType": "self", "args": [2000, 0] }]
+        },
+        {
+            "ratio": 10000,
+            "cooldown": 0,
+            "conditions": [
+                { "type": "directional_attack", "actorType": "self", "arg": 1 },
+                { "type": "hp_less", "actorType": "target", "arg": 50 }
+            ],
+            "actions": [{ "actionType": "modify_critical_ratio", "actorType": "self", "args": [1000] }]
+        },
+        {
+            "ratio": 10000,
+            "cooldown": 0,
+            "conditions": [{ "type": "some_new_condition", "actorType": "self", "arg": 7 }],
+            "actions": [{ "actionType": "some_new_action", "actorType": "self", "args": [1, 2] }]
+        },
+        {
+            "ratio": 5000,
+            "cooldown": 1000,
+            "conditions": [],
+            "actions": [{ "actionType": "modify_final_damage", "actorType": "self", "args": [500] }]
+        }
+    ]
+}"#;
+
+fn effect() -> CombatEffectData {
+    serde_json::from_str(EFFECT).unwrap()
+}
+
+fn firing(effect: &CombatEffectData, hit: &HitContext) -> Vec<usize> {
+    effect
+        .effects
+        .iter()
+        .enumerate()
+        .filter(|(_, detail)| detail.fires(hit))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[test]
+fn decodes_conditions_and_actions() {
+    let effect = effect();
+
+    let first = &effect.effects[0];
+    assert_eq!(first.conditions[0].actor_type, CombatEffectActor::SelfActor);
+    assert_eq!(first.conditions[0].condition(), CombatEffectConditionKind::CurrentSkill { skill_id: 16300 });
+    assert_eq!(first.conditions[1].condition(), CombatEffectConditionKind::TargetCount { count: 1 });
+    assert_eq!(first.actions[0].action(), CombatEffectActionKind::ModifyDamage { rate: 2000 });
+
+    let second = &effect.effects[1];
+    assert_eq!(second.conditions[1].actor_type, CombatEffectActor::Target);
+    assert_eq!(second.conditions[1].condition(), CombatEffectConditionKind::HpLess { percent: 50 });
+
+    let unknown = &effect.effects[2];
+    assert_eq!(
+        unknown.conditions[0].condition(),
+        CombatEffectConditionKind::Unknown { condition_type: "some_new_condition", arg: 7 }
+    );
+    assert_eq!(
+        unknown.actions[0].action(),
+        CombatEffectActionKind::Unknown { action_type: "some_new_action", args: &[1, 2] }
+    );
+}
+
+#[test]
+fn fires_effects_whose_conditions_hold() {
+    let effect = effect();
+
+    // nothing but the unconditional effect
+    assert_eq!(firing(&effect, &HitContext::default()), [3]);
+
+    let hit = HitContext { skill_id: 16300, ..Default::default() };
+    assert_eq!(firing(&effect, &hit), [0, 3]);
+
+    let hit = HitContext { skill_id: 16300, target_count: 3, ..Default::default() };
+    assert_eq!(firing(&effect, &hit), [3]);
+}
+
+#[test]
+fn checks_the_right_actor_and_direction() {
+    let effect = effect();
+    let low_target = ActorState { hp_percent: 30.0, ..Default::default() };
+
+    let hit = HitContext { hit_option: HitOption::BACK_ATTACK, target: low_target.clone(), ..Default::default() };
+    assert_eq!(firing(&effect, &hit), [1, 3]);
+
+    let hit = HitContext { hit_option: HitOption::FRONTAL_ATTACK, target: low_target.clone(), ..Default::default() };
+    assert_eq!(firing(&effect, &hit), [3]);
+
+    // the caster being low does not count for a target condition
+    let hit = HitContext { hit_option: HitOption::BACK_ATTACK, caster: low_target, ..Default::default() };
+    assert_eq!(firing(&effect, &hit), [3]);
+}
+
+#[test]
+fn critical_includes_dot_crits() {
+    let effect: CombatEffectData = serde_json::from_str(
+        r#"{ "effects": [{ "ratio": 0, "cooldown": 0, "conditions": [{ "type": "critical", "actorType": "self", "arg": 0 }], "actions": [] }] }"#,
+    )
+    .unwrap();
+
+    assert!(effect.firing(&HitContext { hit_flag: HitFlag::CRITICAL, ..Default::default() }).next().is_some());
+    assert!(effect.firing(&HitContext { hit_flag: HitFlag::DOT_CRITICAL, ..Default::default() }).next().is_some());
+    assert!(effect.firing(&HitContext { hit_flag: HitFlag::DOT, ..Default::default() }).next().is_none());
+}
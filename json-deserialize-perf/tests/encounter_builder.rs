@@ -0,0 +1,249 @@
+use json_deserialize_perf::encounter_builder::{DamageEvent, EncounterBuilder};
+use json_deserialize_perf::models::{DamageData, EncounterEntity, EntityType, HitFlag, HitOption};
+
+const START: i64 = 1_700_000_000_000;
+
+const PLAYER: u64 = 1;
+const OTHER_PLAYER: u64 = 2;
+const BOSS: u64 = 10;
+
+fn entity(id: u64, name: &str, entity_type: EntityType) -> EncounterEntity {
+    EncounterEntity { id, name: name.into(), entity_type, ..Default::default() }
+}
+
+fn builder() -> EncounterBuilder {
+    let mut builder = EncounterBuilder::new();
+    builder.add_entity(entity(PLAYER, "Sorc", EntityType::PLAYER));
+    builder.add_entity(entity(OTHER_PLAYER, "Bard", EntityType::PLAYER));
+    builder.add_entity(entity(BOSS, "Thaemine", EntityType::BOSS));
+    builder
+}
+
+fn hit(
+    source_id: u64,
+    target_id: u64,
+    skill_id: u32,
+    damage: i64,
+    flag: HitFlag,
+    option: HitOption,
+    at: i64,
+) -> DamageEvent {
+    DamageEvent {
+        source_id,
+        target_id,
+        damage: DamageData {
+            skill_id,
+            skill_effect_id: 0,
+            damage,
+            shield_damage: None,
+            modifier: 0,
+            target_current_hp: 1_000_000 - damage,
+            target_max_hp: 1_000_000,
+            damage_attribute: None,
+            damage_type: 0,
+        },
+        hit_flag: flag,
+        hit_option: option,
+        timestamp: START + at,
+    }
+}
+
+#[test]
+fn skill_totals_follow_the_script() {
+    let mut builder = builder();
+
+    assert!(builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 0)));
+    assert!(builder.ingest(hit(PLAYER, BOSS, 100, 3000, HitFlag::CRITICAL, HitOption::BACK_ATTACK, 200)));
+    assert!(builder.cast(PLAYER, 100, START + 2000));
+    let dot = hit(PLAYER, BOSS, 100, 1500, HitFlag::DOT_CRITICAL, HitOption::FRONTAL_ATTACK, 2100);
+    assert!(builder.ingest(dot));
+    assert!(builder.ingest(hit(PLAYER, BOSS, 200, 500, HitFlag::DOT, HitOption::NONE, 4000)));
+
+    let encounter = builder.finish();
+    let sorc = &encounter.entities["Sorc"];
+    let skill = &sorc.skills[&100];
+
+    assert_eq!(skill.total_damage, 5500);
+    assert_eq!(skill.max_damage, 3000);
+    assert_eq!(skill.max_damage_cast, 4000);
+    assert_eq!(skill.hits, 3);
+    assert_eq!(skill.crits, 2);
+    assert_eq!(skill.crit_damage, 4500);
+    assert_eq!(skill.back_attacks, 1);
+    assert_eq!(skill.back_attack_damage, 3000);
+    assert_eq!(skill.front_attacks, 1);
+    assert_eq!(skill.front_attack_damage, 1500);
+    assert_eq!(skill.casts, 2);
+    assert_eq!(skill.cast_log, [0, 2000]);
+    assert_eq!(skill.skill_cast_log.len(), 2);
+    assert_eq!(skill.skill_cast_log[0].last, 200);
+    assert_eq!(skill.skill_cast_log[0].hits.len(), 2);
+    assert_eq!(skill.skill_cast_log[1].hits[0].timestamp, 2100);
+    assert_eq!(skill.last_timestamp, START + 2100);
+    assert_eq!(skill.dps, 1375);
+
+    assert_eq!(sorc.skills[&200].casts, 1);
+    assert_eq!(sorc.skills[&200].crits, 0);
+
+    assert_eq!(sorc.damage_stats.damage_dealt, 6000);
+    assert_eq!(sorc.damage_stats.crit_damage, 4500);
+    assert_eq!(sorc.damage_stats.back_attack_damage, 3000);
+    assert_eq!(sorc.damage_stats.front_attack_damage, 1500);
+    assert_eq!(sorc.damage_stats.dps, 1500);
+    assert_eq!(sorc.skill_stats.casts, 3);
+    assert_eq!(sorc.skill_stats.hits, 4);
+    assert_eq!(sorc.skill_stats.crits, 2);
+    assert_eq!(sorc.skill_stats.back_attacks, 1);
+    assert_eq!(sorc.skill_stats.front_attacks, 1);
+}
+
+#[test]
+fn encounter_totals_follow_the_script() {
+    let mut builder = builder();
+
+    builder.ingest(hit(PLAYER, BOSS, 100, 4000, HitFlag::NORMAL, HitOption::NONE, 0));
+    builder.ingest(hit(OTHER_PLAYER, BOSS, 300, 1000, HitFlag::NORMAL, HitOption::NONE, 1000));
+    builder.ingest(hit(BOSS, OTHER_PLAYER, 900, 700, HitFlag::NORMAL, HitOption::NONE, 1500));
+    builder.ingest(hit(BOSS, PLAYER, 900, 200, HitFlag::NORMAL, HitOption::NONE, 1800));
+    builder.ingest(hit(OTHER_PLAYER, BOSS, 300, 2000, HitFlag::NORMAL, HitOption::NONE, 2000));
+
+    let encounter = builder.finish();
+    let stats = &encounter.encounter_damage_stats;
+
+    assert_eq!(encounter.fight_start, START);
+    assert_eq!(encounter.last_combat_packet, START + 2000);
+    assert_eq!(encounter.duration, 2000);
+
+    assert_eq!(stats.total_damage_dealt, 7000);
+    assert_eq!(stats.top_damage_dealt, 4000);
+    assert_eq!(stats.total_damage_taken, 900);
+    assert_eq!(stats.top_damage_taken, 700);
+    assert_eq!(stats.dps, 3500);
+
+    let boss = &encounter.entities["Thaemine"];
+    assert_eq!(boss.damage_stats.damage_taken, 7000);
+    assert_eq!(boss.damage_stats.damage_dealt, 900);
+    assert_eq!(boss.current_hp, 998_000);
+    assert_eq!(encounter.current_boss_name, "Thaemine");
+    assert_eq!(encounter.current_boss.as_ref().map(|boss| boss.id), Some(BOSS));
+}
+
+#[test]
+fn dropped_events_change_nothing() {
+    let mut builder = builder();
+
+    assert!(!builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::INVINCIBLE, HitOption::NONE, 0)));
+    assert!(!builder.ingest(hit(PLAYER, BOSS, 0, 1000, HitFlag::DAMAGE_SHARE, HitOption::NONE, 0)));
+    assert!(!builder.ingest(hit(99, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 0)));
+    assert!(!builder.ingest(hit(PLAYER, 99, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 0)));
+    assert!(!builder.cast(99, 100, START));
+
+    let encounter = builder.finish();
+    assert_eq!(encounter.fight_start, 0);
+    assert_eq!(encounter.encounter_damage_stats.total_damage_dealt, 0);
+    assert!(encounter.entities.values().all(|entity| entity.skills.is_empty()));
+    assert!(encounter.current_boss.is_none());
+}
+
+#[test]
+fn replaying_a_script_is_deterministic() {
+    let run = || {
+        let mut builder = builder();
+        for step in 0..50 {
+            let (flag, option) = match step % 4 {
+                0 => (HitFlag::CRITICAL, HitOption::BACK_ATTACK),
+                1 => (HitFlag::NORMAL, HitOption::FRONTAL_ATTACK),
+                2 => (HitFlag::DOT, HitOption::NONE),
+                _ => (HitFlag::NORMAL, HitOption::FLANK_ATTACK),
+            };
+            let source = if step % 3 == 0 { OTHER_PLAYER } else { PLAYER };
+            builder.ingest(hit(source, BOSS, 100 + step as u32 % 5, 100 * step, flag, option, 250 * step));
+        }
+        // entity and skill maps iterate in hash order, compare as values
+        serde_json::to_value(builder.finish()).unwrap()
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn casts_before_the_first_hit_are_placed_before_it() {
+    let mut builder = builder();
+
+    assert!(builder.cast(PLAYER, 100, START - 500));
+    assert!(builder.cast(PLAYER, 200, START - 300));
+    builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 0));
+    builder.ingest(hit(PLAYER, BOSS, 100, 2000, HitFlag::NORMAL, HitOption::NONE, 100));
+    builder.ingest(hit(PLAYER, BOSS, 200, 700, HitFlag::NORMAL, HitOption::NONE, 200));
+
+    let encounter = builder.finish();
+    let sorc = &encounter.entities["Sorc"];
+    let skill = &sorc.skills[&100];
+
+    assert_eq!(encounter.fight_start, START);
+    // the hits land on the cast that was seen, no implicit one is added
+    assert_eq!(skill.casts, 1);
+    assert_eq!(skill.cast_log, [-500]);
+    assert_eq!(skill.skill_cast_log[0].timestamp, -500);
+    assert_eq!(skill.skill_cast_log[0].last, 100);
+    assert_eq!(skill.skill_cast_log[0].hits.len(), 2);
+    assert_eq!(skill.max_damage_cast, 3000);
+    assert_eq!(sorc.skills[&200].cast_log, [-300]);
+    assert_eq!(sorc.skills[&200].skill_cast_log[0].hits[0].timestamp, 200);
+    assert_eq!(sorc.skill_stats.casts, 2);
+}
+
+#[test]
+fn out_of_order_events_keep_the_latest_time() {
+    let mut builder = builder();
+
+    builder.cast(PLAYER, 100, START + 2000);
+    builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 0));
+    builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 3000));
+    builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 1000));
+    builder.ingest(hit(PLAYER, BOSS, 100, 500, HitFlag::NORMAL, HitOption::NONE, 2500));
+
+    let encounter = builder.finish();
+    let skill = &encounter.entities["Sorc"].skills[&100];
+
+    assert_eq!(encounter.duration, 3000);
+    assert_eq!(encounter.last_combat_packet, START + 3000);
+    assert_eq!(skill.dps, 1166);
+    assert_eq!(skill.last_timestamp, START + 3000);
+
+    // hits before the seen cast start an implicit one ahead of it
+    assert_eq!(skill.casts, 2);
+    assert_eq!(skill.cast_log, [0, 2000]);
+    let first = &skill.skill_cast_log[0];
+    assert_eq!((first.timestamp, first.last), (0, 1000));
+    let second = &skill.skill_cast_log[1];
+    assert_eq!((second.timestamp, second.last), (2000, 3000));
+    let hit_times: Vec<i64> = second.hits.iter().map(|hit| hit.timestamp).collect();
+    assert_eq!(hit_times, [2500, 3000]);
+    assert_eq!(skill.max_damage_cast, 2000);
+}
+
+#[test]
+fn hits_before_fight_start_move_it_back() {
+    let mut builder = builder();
+
+    builder.ingest(hit(PLAYER, BOSS, 100, 1000, HitFlag::NORMAL, HitOption::NONE, 1000));
+    builder.cast(PLAYER, 200, START + 1500);
+    builder.ingest(hit(PLAYER, BOSS, 200, 700, HitFlag::NORMAL, HitOption::NONE, 1600));
+    // older than the first hit and the boss hp it reports is stale
+    let mut late = hit(OTHER_PLAYER, BOSS, 300, 400, HitFlag::NORMAL, HitOption::NONE, 0);
+    late.damage.target_current_hp = 1_000_000;
+    builder.ingest(late);
+
+    let encounter = builder.finish();
+    let sorc = &encounter.entities["Sorc"];
+
+    assert_eq!(encounter.fight_start, START);
+    assert_eq!(encounter.duration, 1600);
+    assert_eq!(sorc.skills[&100].cast_log, [1000]);
+    assert_eq!(sorc.skills[&100].skill_cast_log[0].hits[0].timestamp, 1000);
+    assert_eq!(sorc.skills[&200].cast_log, [1500]);
+    assert_eq!(sorc.skills[&200].skill_cast_log[0].hits[0].timestamp, 1600);
+    assert_eq!(encounter.entities["Bard"].skills[&300].cast_log, [0]);
+    assert_eq!(encounter.entities["Thaemine"].current_hp, 1_000_000 - 700);
+}
@@ -0,0 +1,131 @@
+use hashbrown::{HashMap, HashSet};
+use json_deserialize_perf::models::{
+    BossHpLog, Encounter, EncounterDamageStats, EncounterEntity, EntityType, Skill, SkillCast, SkillHit,
+    StatusEffect, StatusEffectCategory,
+};
+
+const FIGHT_START: i64 = 1_700_000_000_000;
+
+fn skill(id: u32, cast_log: Vec<i32>, skill_cast_log: Vec<SkillCast>) -> Skill {
+    Skill {
+        id,
+        name: "Bloody Rush".into(),
+        total_damage: 1_000_000,
+        max_damage: 600_000,
+        buffed_by: HashMap::from([(211606, 600_000), (999_999, 400_000)]),
+        debuffed_by: HashMap::from([(210230, 1_000_000)]),
+        casts: cast_log.len() as i64,
+        hits: 2,
+        crits: 1,
+        cast_log,
+        skill_cast_log,
+        last_timestamp: 123,
+        ..Default::default()
+    }
+}
+
+fn encounter() -> Encounter {
+    let hit = SkillHit {
+        timestamp: 4_200,
+        damage: 600_000,
+        crit: true,
+        buffed_by: vec![211606],
+        ..Default::default()
+    };
+    let casts = vec![
+        SkillCast { timestamp: 1_000, last: 1_500, hits: vec![] },
+        SkillCast { timestamp: 4_000, last: 4_200, hits: vec![hit] },
+    ];
+
+    let mut player = EncounterEntity {
+        id: 1,
+        name: "Player".into(),
+        entity_type: EntityType::PLAYER,
+        class_id: 102,
+        class: "Berserker".into(),
+        gear_score: 1680.5,
+        ..Default::default()
+    };
+    player.skills.insert(16140, skill(16140, vec![1_000, 4_000], casts));
+    player.skills.insert(16020, skill(16020, vec![2_500], vec![]));
+    player.damage_stats.damage_dealt = 2_000_000;
+
+    let boss = EncounterEntity {
+        id: 2,
+        npc_id: 480_010,
+        name: "Valtan".into(),
+        entity_type: EntityType::BOSS,
+        max_hp: 1_000_000_000,
+        ..Default::default()
+    };
+
+    let buff = StatusEffect { category: StatusEffectCategory::Buff, ..Default::default() };
+    let debuff = StatusEffect { category: StatusEffectCategory::Debuff, ..Default::default() };
+
+    Encounter {
+        last_combat_packet: FIGHT_START + 5_000,
+        fight_start: FIGHT_START,
+        local_player: "Player".into(),
+        entities: HashMap::from([("Player".into(), player), ("Valtan".into(), boss.clone())]),
+        current_boss_name: "Valtan".into(),
+        current_boss: Some(boss),
+        encounter_damage_stats: EncounterDamageStats {
+            total_damage_dealt: 2_000_000,
+            buffs: HashMap::from([(211606, buff)]),
+            debuffs: HashMap::from([(210230, debuff)]),
+            unknown_buffs: HashSet::from([1]),
+            boss_hp_log: HashMap::from([("Valtan".into(), vec![BossHpLog::new(1, 900, 0.9)])]),
+            ..Default::default()
+        },
+        duration: 5_000,
+        difficulty: Some("Hard".into()),
+        cleared: true,
+        region: Some("EUC".into()),
+        ..Default::default()
+    }
+}
+
+// compared as values since map order is not stable, and through text so f32s are not widened
+fn assert_round_trip(json: &str, reloaded: &Encounter) {
+    let original: serde_json::Value = serde_json::from_str(json).unwrap();
+    let reloaded: serde_json::Value = serde_json::from_str(&serde_json::to_string(reloaded).unwrap()).unwrap();
+    assert_eq!(reloaded, original);
+}
+
+fn assert_rebuilt(reloaded: &Encounter) {
+    let skills = &reloaded.entities["Player"].skills;
+    assert_eq!(skills[&16140].last_timestamp, FIGHT_START + 4_200);
+    assert_eq!(skills[&16020].last_timestamp, FIGHT_START + 2_500);
+    assert_eq!(reloaded.entities["Valtan"].skills.len(), 0);
+
+    assert_eq!(reloaded.encounter_damage_stats.unknown_buffs, HashSet::from([999_999]));
+}
+
+#[test]
+fn round_trips_through_serde_json() {
+    let json = serde_json::to_string(&encounter()).unwrap();
+    let reloaded: Encounter = serde_json::from_str(&json).unwrap();
+
+    assert_round_trip(&json, &reloaded);
+    assert_rebuilt(&reloaded);
+}
+
+#[test]
+fn round_trips_through_simd_json() {
+    let json = serde_json::to_string(&encounter()).unwrap();
+    let mut bytes = json.clone().into_bytes();
+    let reloaded: Encounter = simd_json::from_slice(&mut bytes).unwrap();
+
+    assert_round_trip(&json, &reloaded);
+    assert_rebuilt(&reloaded);
+}
+
+#[test]
+fn skipped_optional_fields_come_back_empty() {
+    let json = serde_json::to_string(&Encounter::default()).unwrap();
+    assert!(!json.contains("region") && !json.contains("sync"));
+
+    let reloaded: Encounter = serde_json::from_str(&json).unwrap();
+    assert!(reloaded.region.is_none() && reloaded.current_boss.is_none());
+    assert!(reloaded.encounter_damage_stats.unknown_buffs.is_empty());
+}
@@ -0,0 +1,59 @@
+#![cfg(not(feature = "slim-engravings"))]
+
+use hashbrown::HashMap;
+use json_deserialize_perf::assets::{asset_path, parse_json_str, AssetSource};
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::models::EngravingData;
+use json_deserialize_perf::models_ref::EngravingDataRef;
+
+fn engravings() -> HashMap<u32, EngravingData> {
+    ReaderSource.load("Ability.json").unwrap()
+}
+
+#[test]
+fn reads_levels_and_addons() {
+    let engravings = engravings();
+    let disrespect = &engravings[&107];
+
+    assert_eq!(disrespect.feature_type, "ruthless");
+    assert!(disrespect.is_engrave);
+    assert!(!disrespect.is_penalty);
+    assert_eq!(disrespect.max_level(), Some(3));
+
+    let level = disrespect.level(2).unwrap();
+    assert_eq!(level.level, 2);
+    assert_eq!(level.values.as_deref().map(|values| &values[..2]), Some(&[3000, 2200][..]));
+
+    let addon = &level.addons.as_ref().unwrap()[0];
+    assert_eq!(addon.addon_type, "ability_feature");
+    assert_eq!(addon.stat_type, "none");
+    assert_eq!((addon.key_index, addon.key_value), (107, 2));
+}
+
+#[test]
+fn flags_penalty_engravings() {
+    let engravings = engravings();
+    let penalties: Vec<_> = engravings.values().filter(|engraving| engraving.is_penalty).collect();
+
+    assert_eq!(penalties.len(), 8);
+    assert!(penalties.iter().any(|engraving| engraving.name.as_deref() == Some("Atk. Power Reduction")));
+}
+
+#[test]
+fn borrowed_model_reads_the_same_fields() {
+    let contents = std::fs::read_to_string(asset_path("Ability.json")).unwrap();
+    let borrowed: HashMap<u32, EngravingDataRef> = parse_json_str("Ability.json", &contents).unwrap();
+    let owned = engravings();
+
+    assert_eq!(borrowed.len(), owned.len());
+    for (id, engraving) in &owned {
+        let other = &borrowed[id];
+        assert_eq!(other.feature_type, engraving.feature_type.as_str(), "{id}");
+        assert_eq!(other.is_engrave, engraving.is_engrave, "{id}");
+        assert_eq!(other.is_penalty, engraving.is_penalty, "{id}");
+        assert_eq!(other.levels.len(), engraving.levels.len(), "{id}");
+    }
+
+    let addon = &borrowed[&107].levels[&2].addons.as_ref().unwrap()[0];
+    assert_eq!((addon.addon_type.as_ref(), addon.key_index, addon.key_value), ("ability_feature", 107, 2));
+}
@@ -0,0 +1,60 @@
+use serde::de::DeserializeOwned;
+
+use json_deserialize_perf::assets::{parse_json_str, AssetSource};
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::enums::EnumRegistry;
+use json_deserialize_perf::error::AssetError;
+
+// serves one inline file, whatever name is asked for
+struct InlineSource(&'static str);
+
+impl AssetSource for InlineSource {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        parse_json_str(name, self.0)
+    }
+}
+
+const ENUMS: &str = r#"{
+    "afkkickchecktype": { "0": "none", "3": "damage" },
+    "broken": ["not", "an", "enum"]
+}"#;
+
+#[test]
+fn eager_and_lazy_agree() {
+    let eager = EnumRegistry::load(&mut ReaderSource).unwrap();
+    let lazy = EnumRegistry::load_lazy(&mut ReaderSource).unwrap();
+
+    assert_eq!(eager.len(), lazy.len());
+    assert_eq!(lazy.name_of("afkkickchecktype", 3).unwrap(), Some("damage"));
+    assert_eq!(lazy.id_of("afkkickchecktype", "damage").unwrap(), Some(3));
+    for name in eager.enum_names() {
+        assert_eq!(eager.get(name).unwrap().unwrap().len(), lazy.get(name).unwrap().unwrap().len(), "{name}");
+    }
+}
+
+#[test]
+fn unknown_enums_are_not_an_error() {
+    let registry = EnumRegistry::load_lazy(&mut InlineSource(ENUMS)).unwrap();
+
+    assert!(registry.get("missing").unwrap().is_none());
+    assert_eq!(registry.name_of("missing", 0).unwrap(), None);
+    assert_eq!(registry.name_of("afkkickchecktype", 1).unwrap(), None);
+}
+
+#[test]
+fn malformed_enum_fails_its_lookups() {
+    let registry = EnumRegistry::load_lazy(&mut InlineSource(ENUMS)).unwrap();
+
+    assert!(registry.contains("broken"));
+    for _ in 0..2 {
+        let err = registry.get("broken").unwrap_err();
+        assert!(matches!(err, AssetError::Parse { file, .. } if file.contains("broken")), "{err}");
+    }
+    // the rest of the file is unaffected
+    assert_eq!(registry.name_of("afkkickchecktype", 0).unwrap(), Some("none"));
+}
+
+#[test]
+fn malformed_enum_fails_eager_load() {
+    assert!(matches!(EnumRegistry::load(&mut InlineSource(ENUMS)), Err(AssetError::Parse { .. })));
+}
@@ -0,0 +1,120 @@
+use hashbrown::HashMap;
+
+use json_deserialize_perf::assets::{
+    parse_json_reader, parse_json_slice, parse_json_str, parse_simd_slice, AssetSource,
+};
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::deser_reader_simd::SimdReaderSource;
+use json_deserialize_perf::error::{AssetError, ParseLocation};
+use json_deserialize_perf::models::SkillData;
+
+type Skills = HashMap<u32, SkillData>;
+
+// `"id"` on the second line holds a string where an integer is expected
+const TYPE_ERROR: &str = "{\n  \"1\": { \"id\": \"x\", \"classId\": 102 }\n}";
+// the second entry has no key
+const SYNTAX_ERROR: &str = "{\n  \"1\": { \"id\": 1, \"classId\": 102 },, \n}";
+
+fn simd<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, AssetError> {
+    let mut bytes = json.as_bytes().to_vec();
+    parse_simd_slice("Skill.json", &mut bytes, || Ok(json.as_bytes().to_vec()))
+}
+
+fn parse_error(err: AssetError) -> (String, ParseLocation, String, String) {
+    match err {
+        AssetError::Parse { file, location, path, message } => (file, location, path, message),
+        err => panic!("not a parse error: {err}"),
+    }
+}
+
+#[test]
+fn missing_files_are_reported_by_both_parsers() {
+    let err = ReaderSource.load::<Skills>("Missing.json").unwrap_err();
+    assert!(matches!(&err, AssetError::MissingFile { path } if path.ends_with("Missing.json")), "{err}");
+    assert_eq!(err.to_string(), "asset file meter-data/Missing.json is missing");
+
+    let err = SimdReaderSource.load::<Skills>("Missing.json").unwrap_err();
+    assert!(matches!(&err, AssetError::MissingFile { path } if path.ends_with("Missing.json")), "{err}");
+}
+
+#[test]
+fn serde_json_type_errors_carry_path_and_position() {
+    let err = parse_json_str::<Skills>("Skill.json", TYPE_ERROR).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "could not parse Skill.json at line 2 column 18 (byte 19), path `1.id`: \
+         invalid type: string \"x\", expected i32"
+    );
+
+    let (file, location, path, message) = parse_error(err);
+    assert_eq!(file, "Skill.json");
+    assert_eq!(location, ParseLocation { line: Some(2), column: Some(18), offset: Some(19) });
+    assert_eq!(&TYPE_ERROR[..19], "{\n  \"1\": { \"id\": \"x");
+    assert_eq!(path, "1.id");
+    assert!(!message.contains("line"), "{message}");
+
+    // the slice parser reports the same
+    let err = parse_json_slice::<Skills>("Skill.json", TYPE_ERROR.as_bytes()).unwrap_err();
+    assert_eq!(parse_error(err).1.offset, Some(19));
+}
+
+#[test]
+fn serde_json_syntax_errors_carry_position() {
+    let err = parse_json_str::<Skills>("Skill.json", SYNTAX_ERROR).unwrap_err();
+    let (_, location, _, message) = parse_error(err);
+
+    assert_eq!(location, ParseLocation { line: Some(2), column: Some(36), offset: Some(37) });
+    assert_eq!(SYNTAX_ERROR.as_bytes()[37], b',');
+    assert_eq!(message, "key must be a string");
+}
+
+#[test]
+fn simd_type_errors_carry_path() {
+    let err = simd::<Skills>(TYPE_ERROR).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "could not parse Skill.json at unknown position, path `1.id`: ExpectedSigned"
+    );
+
+    let (_, location, path, _) = parse_error(err);
+    // simd-json only knows positions while building the tape
+    assert_eq!(location, ParseLocation::default());
+    assert_eq!(path, "1.id");
+}
+
+#[test]
+fn simd_syntax_errors_carry_offset() {
+    let err = simd::<Skills>(SYNTAX_ERROR).unwrap_err();
+    let (_, location, path, message) = parse_error(err);
+
+    assert_eq!(location.offset, Some(37));
+    assert_eq!((location.line, location.column), (None, None));
+    assert_eq!(path, ".");
+    assert_eq!(message, "ExpectedObjectKey");
+}
+
+#[test]
+fn offsets_count_from_the_line_start() {
+    // an error on the first line, and one after a blank line
+    let err = parse_json_str::<Vec<u32>>("List.json", "[1, \"2\"]").unwrap_err();
+    assert_eq!(parse_error(err).1, ParseLocation { line: Some(1), column: Some(7), offset: Some(6) });
+
+    let err = parse_json_str::<Vec<u32>>("List.json", "[\n\n  1,\n  x]").unwrap_err();
+    assert_eq!(parse_error(err).1, ParseLocation { line: Some(4), column: Some(3), offset: Some(10) });
+
+    // the reader parser has no buffer to count in
+    let err = parse_json_reader::<Vec<u32>, _, _>("List.json", || Ok("[1, \"2\"]".as_bytes())).unwrap_err();
+    assert_eq!(parse_error(err).1, ParseLocation { line: Some(1), column: Some(7), offset: None });
+}
+
+#[test]
+fn parse_locations_display_what_is_known() {
+    let full = ParseLocation { line: Some(2), column: Some(5), offset: Some(12) };
+    let lines = ParseLocation { offset: None, ..full };
+    let offset = ParseLocation { offset: Some(12), ..Default::default() };
+
+    assert_eq!(full.to_string(), "line 2 column 5 (byte 12)");
+    assert_eq!(lines.to_string(), "line 2 column 5");
+    assert_eq!(offset.to_string(), "byte 12");
+    assert_eq!(ParseLocation::default().to_string(), "unknown position");
+}
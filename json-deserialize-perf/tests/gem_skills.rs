@@ -0,0 +1,52 @@
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::gem_skills::{GemSkillGroupRegistry, RawGemSkillGroups};
+
+fn registry() -> GemSkillGroupRegistry {
+    ReaderSource.load("GemSkillGroup.json").unwrap()
+}
+
+#[test]
+fn keeps_group_name_and_icon() {
+    let registry = registry();
+    let group = registry.get(11000).unwrap();
+
+    assert_eq!(group.name, "Burst Skill");
+    assert_eq!(group.icon, "bk_skill_01_11");
+    assert_eq!(registry.skills_of(11010), Some(&[16140, 16145, 16146, 16147][..]));
+    assert_eq!(registry.get(110000).map(|group| group.name.as_str()), Some(""));
+    assert!(registry.malformed_keys().next().is_none());
+}
+
+#[test]
+fn indexes_skills_by_group() {
+    let registry = registry();
+
+    let groups: Vec<_> = registry.groups_of(16140).map(|group| group.id).collect();
+    assert_eq!(groups.len(), 5);
+    assert!(groups.is_sorted());
+    assert!(groups.contains(&11000) && groups.contains(&11010));
+    assert_eq!(registry.groups_of(1).count(), 0);
+}
+
+#[test]
+fn reports_malformed_keys() {
+    let registry: GemSkillGroupRegistry = serde_json::from_str(
+        r#"{
+            "11000": ["Burst Skill", "bk_skill_01_11", [16020]],
+            "burst": ["Burst Skill", "bk_skill_01_11", [16140]],
+            "-1": ["", "", []]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(registry.len(), 1);
+    let mut malformed: Vec<_> = registry.malformed_keys().collect();
+    malformed.sort_unstable();
+    assert_eq!(malformed, ["-1", "burst"]);
+    assert_eq!(registry.groups_of(16140).count(), 0);
+
+    // malformed entries survive a round trip through the raw table
+    let raw = RawGemSkillGroups::from(registry);
+    assert_eq!(raw.len(), 3);
+}
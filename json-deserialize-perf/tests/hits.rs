@@ -0,0 +1,148 @@
+use json_deserialize_perf::encounter_builder::DamageEvent;
+use json_deserialize_perf::error::HitDecodeError;
+use json_deserialize_perf::hits::decode_modifier;
+use json_deserialize_perf::models::{DamageData, HitFlag, HitOption};
+
+const FLAGS: [HitFlag; 14] = [
+    HitFlag::NORMAL,
+    HitFlag::CRITICAL,
+    HitFlag::MISS,
+    HitFlag::INVINCIBLE,
+    HitFlag::DOT,
+    HitFlag::IMMUNE,
+    HitFlag::IMMUNE_SILENCED,
+    HitFlag::FONT_SILENCED,
+    HitFlag::DOT_CRITICAL,
+    HitFlag::DODGE,
+    HitFlag::REFLECT,
+    HitFlag::DAMAGE_SHARE,
+    HitFlag::DODGE_HIT,
+    HitFlag::MAX,
+];
+
+const OPTIONS: [HitOption; 5] =
+    [HitOption::NONE, HitOption::BACK_ATTACK, HitOption::FRONTAL_ATTACK, HitOption::FLANK_ATTACK, HitOption::MAX];
+
+fn damage(modifier: i32) -> DamageData {
+    DamageData {
+        skill_id: 1,
+        skill_effect_id: 0,
+        damage: 100,
+        shield_damage: None,
+        modifier,
+        target_current_hp: 0,
+        target_max_hp: 0,
+        damage_attribute: None,
+        damage_type: 0,
+    }
+}
+
+#[test]
+fn every_flag_round_trips() {
+    for flag in FLAGS {
+        assert_eq!(HitFlag::try_from(flag as u32), Ok(flag));
+    }
+    assert_eq!(HitFlag::MAX as u32, 13);
+}
+
+#[test]
+fn out_of_range_flags_are_rejected() {
+    for value in [14, 15, 16, 255, u32::MAX] {
+        assert_eq!(HitFlag::try_from(value), Err(HitDecodeError::Flag(value)));
+    }
+}
+
+#[test]
+fn every_option_round_trips() {
+    for option in OPTIONS {
+        assert_eq!(HitOption::try_from(option as i32), Ok(option));
+    }
+    assert_eq!(HitOption::MAX as i32, 4);
+}
+
+#[test]
+fn out_of_range_options_are_rejected() {
+    for value in [5, 7, 8, i32::MAX, -1, i32::MIN] {
+        assert_eq!(HitOption::try_from(value), Err(HitDecodeError::Option(value)));
+    }
+}
+
+#[test]
+fn flag_helpers() {
+    let crits: Vec<_> = FLAGS.into_iter().filter(|flag| flag.is_crit()).collect();
+    let dots: Vec<_> = FLAGS.into_iter().filter(|flag| flag.is_dot()).collect();
+
+    assert_eq!(crits, [HitFlag::CRITICAL, HitFlag::DOT_CRITICAL]);
+    assert_eq!(dots, [HitFlag::DOT, HitFlag::DOT_CRITICAL]);
+}
+
+#[test]
+fn option_helpers() {
+    let back: Vec<_> = OPTIONS.into_iter().filter(|option| option.is_back_attack()).collect();
+    let front: Vec<_> = OPTIONS.into_iter().filter(|option| option.is_front_attack()).collect();
+
+    assert_eq!(back, [HitOption::BACK_ATTACK]);
+    assert_eq!(front, [HitOption::FRONTAL_ATTACK]);
+}
+
+// literal modifiers rather than ones packed from the enums, the option field is one above `HitOption`
+#[test]
+fn known_modifiers_decode() {
+    assert_eq!(decode_modifier(0x00), Ok((HitFlag::NORMAL, HitOption::NONE)));
+    assert_eq!(decode_modifier(0x10), Ok((HitFlag::NORMAL, HitOption::NONE)));
+    assert_eq!(decode_modifier(0x21), Ok((HitFlag::CRITICAL, HitOption::BACK_ATTACK)));
+    assert_eq!(decode_modifier(0x20), Ok((HitFlag::NORMAL, HitOption::BACK_ATTACK)));
+    assert_eq!(decode_modifier(0x31), Ok((HitFlag::CRITICAL, HitOption::FRONTAL_ATTACK)));
+    assert_eq!(decode_modifier(0x40), Ok((HitFlag::NORMAL, HitOption::FLANK_ATTACK)));
+    assert_eq!(decode_modifier(0x28), Ok((HitFlag::DOT_CRITICAL, HitOption::BACK_ATTACK)));
+    assert_eq!(decode_modifier(0x5b), Ok((HitFlag::DAMAGE_SHARE, HitOption::MAX)));
+}
+
+#[test]
+fn every_flag_decodes_with_every_option_field() {
+    let fields = [0, 1, 2, 3, 4, 5];
+    let options = [
+        HitOption::NONE,
+        HitOption::NONE,
+        HitOption::BACK_ATTACK,
+        HitOption::FRONTAL_ATTACK,
+        HitOption::FLANK_ATTACK,
+        HitOption::MAX,
+    ];
+    for (index, flag) in FLAGS.into_iter().enumerate() {
+        for (field, option) in fields.into_iter().zip(options) {
+            let modifier = index as i32 | field << 4;
+            assert_eq!(decode_modifier(modifier), Ok((flag, option)), "modifier {modifier:#x}");
+            assert_eq!(damage(modifier).hit(), Ok((flag, option)));
+        }
+    }
+}
+
+#[test]
+fn bits_above_the_option_are_ignored() {
+    assert_eq!(decode_modifier(0x81), Ok((HitFlag::CRITICAL, HitOption::NONE)));
+    assert_eq!(decode_modifier(0x7fff_ffa1), Ok((HitFlag::CRITICAL, HitOption::BACK_ATTACK)));
+    assert_eq!(decode_modifier(!0x7f | 0x28), Ok((HitFlag::DOT_CRITICAL, HitOption::BACK_ATTACK)));
+}
+
+#[test]
+fn out_of_range_modifiers_are_rejected() {
+    assert_eq!(decode_modifier(0x0e), Err(HitDecodeError::Flag(14)));
+    assert_eq!(decode_modifier(0x0f), Err(HitDecodeError::Flag(15)));
+    // fields 6 and 7 would be options 5 and 6
+    assert_eq!(decode_modifier(0x60), Err(HitDecodeError::Option(5)));
+    assert_eq!(decode_modifier(0x70), Err(HitDecodeError::Option(6)));
+    // the flag is checked first
+    assert_eq!(decode_modifier(0x7f), Err(HitDecodeError::Flag(15)));
+    assert_eq!(decode_modifier(-1), Err(HitDecodeError::Flag(15)));
+}
+
+#[test]
+fn damage_events_decode_the_modifier() {
+    let event = DamageEvent::decode(1, 2, damage(0x21), 1000).unwrap();
+    assert_eq!((event.hit_flag, event.hit_option), (HitFlag::CRITICAL, HitOption::BACK_ATTACK));
+    assert!(event.hit_flag.is_crit() && event.hit_option.is_back_attack());
+
+    let err = DamageEvent::decode(1, 2, damage(0x0f), 1000).unwrap_err();
+    assert_eq!(err.to_string(), "15 is not a hit flag");
+}
@@ -0,0 +1,80 @@
+use json_deserialize_perf::models::{SkillBuffData, SkillData};
+
+fn skill_json(skill_type: &str) -> String {
+    format!(r#"{{"id": 1, "classId": 102, "type": {skill_type}}}"#)
+}
+
+fn buff_json(buff_type: &str) -> String {
+    format!(
+        r#"{{"id": 1, "duration": 0, "category": "buff", "type": {buff_type}, "target": "self",
+            "uniqueGroup": 0, "overlap": 0, "perLevelData": {{}}}}"#
+    )
+}
+
+fn skill_type_serde(skill_type: &str) -> Result<String, serde_json::Error> {
+    serde_json::from_str::<SkillData>(&skill_json(skill_type)).map(|skill| skill.skill_type.to_string())
+}
+
+fn skill_type_simd(skill_type: &str) -> Result<String, simd_json::Error> {
+    let mut bytes = skill_json(skill_type).into_bytes();
+    simd_json::from_slice::<SkillData>(&mut bytes).map(|skill| skill.skill_type.to_string())
+}
+
+fn buff_type_serde(buff_type: &str) -> Result<String, serde_json::Error> {
+    serde_json::from_str::<SkillBuffData>(&buff_json(buff_type)).map(|buff| buff.buff_type.to_string())
+}
+
+fn buff_type_simd(buff_type: &str) -> Result<String, simd_json::Error> {
+    let mut bytes = buff_json(buff_type).into_bytes();
+    simd_json::from_slice::<SkillBuffData>(&mut bytes).map(|buff| buff.buff_type.to_string())
+}
+
+const ACCEPTED: [(&str, &str); 7] = [
+    (r#""normal""#, "normal"),
+    (r#""""#, ""),
+    ("0", "0"),
+    ("-15", "-15"),
+    ("18446744073709551615", "18446744073709551615"),
+    ("1.5", "1.5"),
+    ("2.0", "2.0"),
+];
+
+const REJECTED: [&str; 5] = ["true", "null", "[1]", r#"{"a": 1}"#, "[]"];
+
+#[test]
+fn accepts_strings_and_numbers_under_serde_json() {
+    for (input, expected) in ACCEPTED {
+        assert_eq!(skill_type_serde(input).unwrap(), expected, "input {input}");
+        assert_eq!(buff_type_serde(input).unwrap(), expected, "input {input}");
+    }
+}
+
+#[test]
+fn accepts_strings_and_numbers_under_simd_json() {
+    for (input, expected) in ACCEPTED {
+        assert_eq!(skill_type_simd(input).unwrap(), expected, "input {input}");
+        assert_eq!(buff_type_simd(input).unwrap(), expected, "input {input}");
+    }
+}
+
+#[test]
+fn rejects_other_types_under_serde_json() {
+    for input in REJECTED {
+        assert!(skill_type_serde(input).is_err(), "input {input}");
+        assert!(buff_type_serde(input).is_err(), "input {input}");
+    }
+}
+
+#[test]
+fn rejects_other_types_under_simd_json() {
+    for input in REJECTED {
+        assert!(skill_type_simd(input).is_err(), "input {input}");
+        assert!(buff_type_simd(input).is_err(), "input {input}");
+    }
+}
+
+#[test]
+fn missing_skill_type_defaults_to_empty() {
+    let skill: SkillData = serde_json::from_str(r#"{"id": 1, "classId": 102}"#).unwrap();
+    assert_eq!(skill.skill_type, "");
+}
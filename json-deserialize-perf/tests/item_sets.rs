@@ -0,0 +1,70 @@
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::item_sets::ItemSetRegistry;
+
+fn registry() -> ItemSetRegistry {
+    ReaderSource.load("ItemSet.json").unwrap()
+}
+
+const SUNSET: [u32; 5] = [113112110, 113112111, 113112112, 113112113, 113112114];
+
+#[test]
+fn indexes_items_by_set() {
+    let registry = registry();
+
+    assert_eq!(registry.set_of(113112110).map(|set| set.name.as_str()), Some("Sunset"));
+    assert_eq!(registry.set_of(111131115).map(|set| set.id), Some(10401101));
+    assert!(registry.set_of(1).is_none());
+    assert_eq!(registry.get("Sunset").map(|set| set.levels.len()), Some(2));
+}
+
+#[test]
+fn grants_bonuses_by_piece_count() {
+    let registry = registry();
+
+    let equipped: Vec<_> = SUNSET[..3].iter().map(|id| (*id, 1)).collect();
+    let bonuses = registry.active_bonuses(&equipped);
+    assert_eq!(bonuses.len(), 1);
+    assert_eq!((bonuses[0].count, bonuses[0].level), (2, 1));
+
+    let equipped: Vec<_> = SUNSET.iter().map(|id| (*id, 1)).collect();
+    let counts: Vec<_> = registry.active_bonuses(&equipped).iter().map(|bonus| bonus.count).collect();
+    assert_eq!(counts, [2, 5]);
+}
+
+#[test]
+fn bonus_level_follows_the_weakest_counted_piece() {
+    let registry = registry();
+
+    let equipped = [(SUNSET[0], 2), (SUNSET[1], 2), (SUNSET[2], 2), (SUNSET[3], 2), (SUNSET[4], 1)];
+    let levels: Vec<_> = registry
+        .active_bonuses(&equipped)
+        .iter()
+        .map(|bonus| (bonus.count, bonus.level))
+        .collect();
+    assert_eq!(levels, [(2, 2), (5, 1)]);
+
+    // levels above the highest defined one fall back to it
+    let equipped: Vec<_> = SUNSET.iter().map(|id| (*id, 9)).collect();
+    assert!(registry.active_bonuses(&equipped).iter().all(|bonus| bonus.level == 2));
+}
+
+#[test]
+fn counts_each_item_once_and_ignores_unknown_items() {
+    let registry = registry();
+
+    let equipped = [(SUNSET[0], 1), (SUNSET[0], 1), (1, 1)];
+    assert!(registry.active_bonuses(&equipped).is_empty());
+}
+
+#[test]
+fn skips_padding_options() {
+    let registry = registry();
+
+    let equipped: Vec<_> = [111131110, 111131111].into_iter().map(|id| (id, 1)).collect();
+    let bonuses = registry.active_bonuses(&equipped);
+    assert_eq!(bonuses.len(), 1);
+
+    let options: Vec<_> = bonuses[0].options().map(|option| (option.key_stat.as_str(), option.value)).collect();
+    assert_eq!(options, [("attack_speed_rate", 800)]);
+}
@@ -0,0 +1,52 @@
+use serde::de::DeserializeOwned;
+
+use json_deserialize_perf::assets::{AssetPreloader, AssetSource};
+use json_deserialize_perf::deser_parallel::load_parallel;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::error::AssetError;
+
+// `Npc.json` is read on a worker thread, `PCData.json` on the calling one
+#[derive(Default)]
+struct WithoutNpcs;
+
+impl AssetSource for WithoutNpcs {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        if name == "Npc.json" {
+            return Err(AssetError::MissingFile { path: name.into() });
+        }
+        ReaderSource.load(name)
+    }
+}
+
+#[derive(Default)]
+struct WithoutClasses;
+
+impl AssetSource for WithoutClasses {
+    fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, AssetError> {
+        if name == "PCData.json" {
+            return Err(AssetError::MissingFile { path: name.into() });
+        }
+        ReaderSource.load(name)
+    }
+}
+
+#[test]
+fn matches_from_source() {
+    let parallel = serde_json::to_value(load_parallel::<ReaderSource>().unwrap()).unwrap();
+    let sequential = serde_json::to_value(AssetPreloader::from_source(&mut ReaderSource).unwrap()).unwrap();
+
+    // tables are hash maps, compared as values
+    assert!(parallel == sequential);
+}
+
+#[test]
+fn worker_errors_are_returned() {
+    let err = load_parallel::<WithoutNpcs>().err().expect("load error");
+    assert!(matches!(&err, AssetError::MissingFile { path } if path == "Npc.json"), "{err}");
+}
+
+#[test]
+fn errors_on_the_calling_thread_are_returned() {
+    let err = load_parallel::<WithoutClasses>().err().expect("load error");
+    assert!(matches!(&err, AssetError::MissingFile { path } if path == "PCData.json"), "{err}");
+}
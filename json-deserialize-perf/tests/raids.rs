@@ -0,0 +1,109 @@
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::deser_reader_simd::SimdReaderSource;
+use json_deserialize_perf::raids::{DuplicateBoss, RaidCatalog};
+
+fn catalog() -> RaidCatalog {
+    ReaderSource.load("encounters.json").unwrap()
+}
+
+#[test]
+fn keeps_raid_gate_and_boss_order() {
+    let catalog = catalog();
+
+    let raids: Vec<_> = catalog.raids().iter().take(3).map(|raid| raid.name.as_str()).collect();
+    assert_eq!(raids, ["Valtan", "Vykas", "Clown"]);
+
+    let valtan = catalog.raid("Valtan").unwrap();
+    assert_eq!(valtan.gates[0].name, "Valtan G1");
+    assert_eq!(valtan.gates[0].bosses, ["Dark Mountain Predator", "Destroyer Lucas", "Leader Lugaru"]);
+    assert!(catalog.raid("Valtan G1").is_none());
+}
+
+#[test]
+fn locates_bosses() {
+    let catalog = catalog();
+
+    assert_eq!(catalog.locate("Kakul"), Some(("Clown", "Clown G2")));
+    assert_eq!(catalog.locate("Covetous Devourer Vykas"), Some(("Vykas", "Vykas G2")));
+    assert_eq!(catalog.locate("Not A Boss"), None);
+    assert!(!catalog.contains_boss("Valtan"));
+}
+
+#[test]
+fn lists_bosses_per_raid() {
+    let catalog = catalog();
+
+    assert_eq!(
+        catalog.bosses_of("Vykas"),
+        ["Incubus Morphe", "Nightmarish Morphe", "Covetous Devourer Vykas", "Covetous Legion Commander Vykas"]
+    );
+
+    // listed once even though it has two gates
+    let brelshaza = catalog.bosses_of("Brelshaza");
+    assert_eq!(brelshaza.iter().filter(|boss| *boss == "Phantom Legion Commander Brelshaza").count(), 1);
+    assert_eq!(brelshaza.len(), 9);
+
+    let all = catalog.all_bosses();
+    assert_eq!(all.first().map(|boss| boss.as_str()), Some("Dark Mountain Predator"));
+    assert!(all.iter().all(|boss| catalog.contains_boss(boss)));
+    assert!(catalog.bosses_of("Chaos Dungeon").is_empty());
+}
+
+#[test]
+fn detects_duplicate_bosses() {
+    let catalog = catalog();
+    let duplicates = catalog.duplicate_bosses();
+
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].boss, "Phantom Legion Commander Brelshaza");
+    assert_eq!(
+        duplicates[0].locations,
+        [("Brelshaza", "Brelshaza G4"), ("Brelshaza", "Brelshaza G6"), ("Act 2: Brelshaza", "Act 2: Brelshaza G2")]
+    );
+}
+
+#[test]
+fn order_survives_other_formats() {
+    let catalog = catalog();
+
+    let simd: RaidCatalog = SimdReaderSource.load("encounters.json").unwrap();
+    assert_eq!(simd.raids(), catalog.raids());
+
+    let bytes = postcard::to_stdvec(&catalog).unwrap();
+    let cached: RaidCatalog = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(cached.raids(), catalog.raids());
+    assert_eq!(cached.duplicate_bosses(), catalog.duplicate_bosses());
+}
+
+// bosses shared by gates, met in the same gate first, plus one listed twice in a single gate
+const SHARED_BOSSES: &str = r#"{
+    "Raid A": {
+        "Raid A G1": ["Echo", "Delta", "Charlie", "Bravo", "Alpha", "Alpha"],
+        "Raid A G2": ["Alpha", "Bravo", "Charlie", "Delta", "Echo", "Solo", "Solo"]
+    },
+    "Raid B": { "Raid B G1": ["Solo"] }
+}"#;
+
+#[test]
+fn duplicates_first_met_in_one_gate_keep_its_order() {
+    let catalog: RaidCatalog = serde_json::from_str(SHARED_BOSSES).unwrap();
+
+    // all but `Solo` are first met in `Raid A G1`, the hash order of the index must not show through
+    let bosses: Vec<_> = catalog.duplicate_bosses().iter().map(|duplicate| duplicate.boss).collect();
+    assert_eq!(bosses, ["Echo", "Delta", "Charlie", "Bravo", "Alpha", "Solo"]);
+}
+
+#[test]
+fn repeats_within_a_gate_are_not_duplicates() {
+    let json = r#"{ "Raid": { "Raid G1": ["Twice", "Twice"], "Raid G2": ["Once"] } }"#;
+    let catalog: RaidCatalog = serde_json::from_str(json).unwrap();
+
+    assert!(catalog.duplicate_bosses().is_empty());
+    assert_eq!(catalog.locations_of("Twice").collect::<Vec<_>>(), [("Raid", "Raid G1")]);
+
+    let catalog: RaidCatalog = serde_json::from_str(SHARED_BOSSES).unwrap();
+    let solo = catalog.duplicate_bosses().into_iter().find(|duplicate| duplicate.boss == "Solo").unwrap();
+    let locations = vec![("Raid A", "Raid A G2"), ("Raid B", "Raid B G1")];
+    assert_eq!(solo, DuplicateBoss { boss: "Solo", locations });
+}
@@ -0,0 +1,60 @@
+use hashbrown::HashMap;
+use json_deserialize_perf::models::{SkillBuffData, SkillData, SkillEffectData};
+use json_deserialize_perf::skill_index::SkillIndex;
+
+const SKILLS: &str = r#"{
+    "16140": { "id": 16140, "name": "Bloody Rush", "classId": 102, "groups": [11000, 11010] },
+    "16020": { "id": 16020, "name": "Finish Strike", "classId": 102, "groups": [11000] },
+    "21140": { "id": 21140, "name": "Sonic Vibration", "classId": 204 },
+    "21141": { "id": 21141, "name": "Sonic Vibration Wave", "classId": 204, "summonSourceSkills": [21140, 21141] }
+}"#;
+
+const BUFFS: &str = r#"{
+    "210230": { "id": 210230, "duration": 0, "category": "debuff", "type": "skill_damage_amplify", "target": "none",
+        "uniqueGroup": 0, "overlap": 0, "perLevelData": {}, "sourceSkills": [21140, 21141] },
+    "210200": { "id": 210200, "duration": 0, "category": "debuff", "type": "stat", "target": "none",
+        "uniqueGroup": 0, "overlap": 0, "perLevelData": {}, "sourceSkills": [21140] },
+    "100000": { "id": 100000, "duration": 0, "category": "buff", "type": "stat", "target": "self",
+        "uniqueGroup": 0, "overlap": 0, "perLevelData": {} }
+}"#;
+
+const EFFECTS: &str = r#"{
+    "11001": { "id": 11001, "comment": "", "values": [], "sourceSkills": [16140] }
+}"#;
+
+fn index() -> SkillIndex {
+    let skills: HashMap<u32, SkillData> = serde_json::from_str(SKILLS).unwrap();
+    let buffs: HashMap<u32, SkillBuffData> = serde_json::from_str(BUFFS).unwrap();
+    let effects: HashMap<u32, SkillEffectData> = serde_json::from_str(EFFECTS).unwrap();
+    SkillIndex::build(&skills, &buffs, &effects)
+}
+
+#[test]
+fn maps_skills_to_buffs_and_effects() {
+    let index = index();
+
+    assert_eq!(index.buffs_of(21140), [210200, 210230]);
+    assert_eq!(index.buffs_of(21141), [210230]);
+    assert_eq!(index.effects_of(16140), [11001]);
+    assert!(index.buffs_of(16140).is_empty());
+    assert!(index.effects_of(1).is_empty());
+}
+
+#[test]
+fn maps_classes_and_groups_to_skills() {
+    let index = index();
+
+    assert_eq!(index.skills_of_class(102), [16020, 16140]);
+    assert_eq!(index.skills_of_class(204), [21140, 21141]);
+    assert_eq!(index.skills_of_group(11000), [16020, 16140]);
+    assert_eq!(index.skills_of_group(11010), [16140]);
+    assert!(index.skills_of_class(999).is_empty());
+}
+
+#[test]
+fn maps_summons_to_their_parent() {
+    let index = index();
+
+    assert_eq!(index.summon_parent(21141), Some(21140));
+    assert_eq!(index.summon_parent(21140), None);
+}
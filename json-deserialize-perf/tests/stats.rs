@@ -0,0 +1,90 @@
+use hashbrown::HashMap;
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::models::SkillBuffData;
+use json_deserialize_perf::stats::{StatKind, StatResolver};
+
+fn stat_type_map() -> HashMap<String, u32> {
+    ReaderSource.load("StatType.json").unwrap()
+}
+
+fn buff(per_level_data: &str) -> SkillBuffData {
+    let json = format!(
+        r#"{{"id": 1, "duration": 0, "category": "buff", "type": "stat", "target": "party",
+            "uniqueGroup": 0, "overlap": 0, "perLevelData": {per_level_data}}}"#
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+fn option(option_type: &str, key_stat: &str, value: i32) -> String {
+    format!(r#"{{"type": "{option_type}", "keyStat": "{key_stat}", "keyIndex": 0, "value": {value}}}"#)
+}
+
+#[test]
+fn classifies_stats() {
+    let stat_type_map = stat_type_map();
+    let resolver = StatResolver::new(&stat_type_map);
+
+    let expected = [
+        ("critical_hit_rate", StatKind::CritRate),
+        ("criticalhit", StatKind::CritRate),
+        ("critical_dam_rate", StatKind::CritDamage),
+        ("str", StatKind::AttackPower),
+        ("weapon_dam", StatKind::AttackPower),
+        ("attack_power_rate", StatKind::AttackPowerRate),
+        ("skill_damage_rate", StatKind::DamageRate),
+        ("fire_dam_rate", StatKind::DamageRate),
+        ("npc_species_devil_dam_rate", StatKind::DamageRate),
+        ("skill_damage_sub_rate_1", StatKind::DamageRate),
+        ("def_pen_rate", StatKind::Penetration),
+        ("def", StatKind::Defense),
+        ("physical_inc_rate", StatKind::Defense),
+        ("endurance", StatKind::Defense),
+        ("rapidity", StatKind::Swiftness),
+        ("attack_speed_rate", StatKind::AttackSpeed),
+        ("move_speed_rate", StatKind::MoveSpeed),
+        ("cooldown_reduction", StatKind::Cooldown),
+        ("max_hp_x", StatKind::Hp),
+        ("combat_mp_recovery_rate", StatKind::Resource),
+        ("paralyzation_point_rate", StatKind::Stagger),
+        ("exp_rate", StatKind::Other),
+    ];
+
+    for (stat, kind) in expected {
+        assert_eq!(resolver.stat_kind(stat), kind, "{stat}");
+    }
+}
+
+#[test]
+fn resolves_options_at_a_level() {
+    let stat_type_map = stat_type_map();
+    let resolver = StatResolver::new(&stat_type_map);
+    let buff = buff(&format!(
+        r#"{{"1": {{"passiveOptions": [{}, {}, {}]}}, "2": {{"passiveOptions": [{}]}}}}"#,
+        option("stat", "critical_hit_rate", 1000),
+        option("none", "none", 0),
+        option("skill_damage", "none", 500),
+        option("stat", "attack_power_rate", 600),
+    ));
+
+    let deltas = resolver.resolve(&buff, 1);
+    assert_eq!(deltas.len(), 2);
+    assert_eq!((deltas[0].kind, deltas[0].stat, deltas[0].value), (StatKind::CritRate, "critical_hit_rate", 1000));
+    assert_eq!(deltas[0].stat_id, Some(74));
+    assert_eq!((deltas[1].kind, deltas[1].stat, deltas[1].stat_id), (StatKind::DamageRate, "skill_damage", None));
+
+    let deltas = resolver.resolve(&buff, 2);
+    assert_eq!(deltas.len(), 1);
+    assert_eq!((deltas[0].kind, deltas[0].value), (StatKind::AttackPowerRate, 600));
+
+    assert!(resolver.resolve(&buff, 3).is_empty());
+}
+
+#[test]
+fn skips_stats_missing_from_stat_type() {
+    let stat_type_map = stat_type_map();
+    let resolver = StatResolver::new(&stat_type_map);
+    let buff = buff(&format!(r#"{{"1": {{"passiveOptions": [{}]}}}}"#, option("stat", "not_a_stat", 1)));
+
+    assert!(resolver.resolve(&buff, 1).is_empty());
+}
@@ -0,0 +1,30 @@
+use json_deserialize_perf::assets::{AssetPreloader, LoadStrategy};
+use json_deserialize_perf::deser_reader::ReaderSource;
+
+const STRATEGIES: [LoadStrategy; 9] = [
+    LoadStrategy::Reader,
+    LoadStrategy::ReaderSimd,
+    LoadStrategy::AllocBuffInOneGo,
+    LoadStrategy::SimdAllocBuffInOneGo,
+    LoadStrategy::IncludeStr,
+    LoadStrategy::ReadString,
+    LoadStrategy::ParallelReader,
+    LoadStrategy::ParallelReaderSimd,
+    LoadStrategy::Mmap,
+];
+
+// tables are hash maps, compare them as values
+fn tables(assets: AssetPreloader) -> serde_json::Value {
+    serde_json::to_value(assets).unwrap()
+}
+
+#[test]
+fn every_strategy_loads_the_same_tables() {
+    let expected = tables(AssetPreloader::from_source(&mut ReaderSource).unwrap());
+
+    for strategy in STRATEGIES {
+        let assets =
+            AssetPreloader::with_strategy(strategy).unwrap_or_else(|err| panic!("{strategy:?}: {err}"));
+        assert!(tables(assets) == expected, "{strategy:?} differs from from_source");
+    }
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::PathBuf;
+
+use json_deserialize_perf::assets::AssetSource;
+use json_deserialize_perf::deser_reader::ReaderSource;
+use json_deserialize_perf::error::AssetError;
+use json_deserialize_perf::support_buffs::{
+    SupportBuffGroup, SupportBuffGroups, SupportBuffKind, SupportBuffOverride, SUPPORT_BUFF_OVERRIDES,
+};
+
+fn shipped() -> SupportBuffGroups {
+    ReaderSource.load("SupportBuffGroup.json").unwrap()
+}
+
+#[test]
+fn ships_the_support_groups() {
+    let groups = shipped();
+
+    assert_eq!(groups.len(), 8);
+    assert_eq!(groups.groups_of(SupportBuffKind::AttackPower), [101105, 101204, 314004, 480030]);
+    assert_eq!(groups.groups_of(SupportBuffKind::Identity), [211400, 310501, 368000, 480018]);
+    assert!(groups.groups_of(SupportBuffKind::Brand).is_empty());
+
+    let serenade = groups.get(211400).unwrap();
+    assert_eq!(serenade.class_id, 204);
+    assert_eq!(serenade.description, "Bard Serenade of Courage");
+    assert_eq!(groups.kind_of(1), None);
+}
+
+#[test]
+fn overrides_replace_and_add_groups() {
+    let mut groups = shipped();
+    let overrides: Vec<SupportBuffGroup> = serde_json::from_str(
+        r#"[
+            { "uniqueGroup": 101204, "classId": 204, "kind": "identity", "description": "moved" },
+            { "uniqueGroup": 999000, "classId": 999, "kind": "brand", "description": "new support" }
+        ]"#,
+    )
+    .unwrap();
+    groups.apply_overrides(overrides);
+
+    assert_eq!(groups.len(), 9);
+    assert_eq!(groups.kind_of(101204), Some(SupportBuffKind::Identity));
+    assert_eq!(groups.get(101204).unwrap().description, "moved");
+    assert_eq!(groups.kind_of(999000), Some(SupportBuffKind::Brand));
+    assert_eq!(groups.groups_of(SupportBuffKind::AttackPower), [101105, 314004, 480030]);
+}
+
+#[test]
+fn rejects_unknown_kinds() {
+    let result: Result<Vec<SupportBuffGroup>, _> =
+        serde_json::from_str(r#"[{ "uniqueGroup": 1, "classId": 1, "kind": "healing", "description": "" }]"#);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn round_trips_through_serde() {
+    let groups = shipped();
+    let json = serde_json::to_string(&groups).unwrap();
+    let back: SupportBuffGroups = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(back.len(), groups.len());
+    for group in groups.iter() {
+        assert_eq!(back.get(group.unique_group), Some(group));
+    }
+}
+
+// an asset directory holding only the user file
+fn overrides_dir(name: &str, overrides: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("json-deserialize-perf-{}-{name}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(SUPPORT_BUFF_OVERRIDES), overrides).unwrap();
+    dir
+}
+
+#[test]
+fn reads_the_user_file_from_the_asset_dir() {
+    let dir = overrides_dir(
+        "user-file",
+        r#"[
+            { "uniqueGroup": 101204, "classId": 204, "kind": "identity", "description": "moved" },
+            { "uniqueGroup": 999000, "classId": 999, "kind": "brand", "description": "new support" },
+            { "uniqueGroup": 480030, "kind": null },
+            { "uniqueGroup": 480018, "kind": null }
+        ]"#,
+    );
+    let groups = shipped().with_user_overrides_from(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(groups.len(), 7);
+    assert_eq!(groups.kind_of(101204), Some(SupportBuffKind::Identity));
+    assert_eq!(groups.kind_of(999000), Some(SupportBuffKind::Brand));
+    assert_eq!(groups.get(480030), None);
+    assert_eq!(groups.groups_of(SupportBuffKind::AttackPower), [101105, 314004]);
+    assert_eq!(groups.groups_of(SupportBuffKind::Identity), [101204, 211400, 310501, 368000]);
+    assert!(groups.is_support_class(999));
+}
+
+#[test]
+fn removing_the_last_group_of_a_class_drops_the_class() {
+    let mut groups = shipped();
+    let bard = groups.iter().filter(|group| group.class_id == 204).map(|group| group.unique_group);
+    let bard: Vec<u32> = bard.collect();
+    assert!(groups.is_support_class(204));
+
+    let removals = bard.into_iter().map(|unique_group| SupportBuffOverride {
+        unique_group,
+        class_id: 0,
+        kind: None,
+        description: Default::default(),
+    });
+    groups.apply_overrides(removals);
+
+    assert!(!groups.is_support_class(204));
+}
+
+#[test]
+fn missing_user_file_changes_nothing() {
+    let dir = overrides_dir("no-user-file", "[]");
+    fs::remove_file(dir.join(SUPPORT_BUFF_OVERRIDES)).unwrap();
+    let groups = shipped().with_user_overrides_from(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(groups.len(), shipped().len());
+}
+
+#[test]
+fn user_file_entries_need_a_kind() {
+    // a forgotten `kind` is not taken as a removal
+    let dir = overrides_dir("no-kind", r#"[{ "uniqueGroup": 101204, "classId": 204 }]"#);
+    let err = shipped().with_user_overrides_from(&dir).unwrap_err();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(matches!(&err, AssetError::Parse { file, .. } if file == SUPPORT_BUFF_OVERRIDES), "{err}");
+    assert!(err.to_string().contains("missing field `kind`"), "{err}");
+}